@@ -1,6 +1,6 @@
 mod utils;
 
-use types::{JSToken, JSTokenContext};
+use types::{JSLowerError, JSToken, JSTokenContext};
 use wasm_bindgen::prelude::*;
 use wlang::{
     ast::{
@@ -44,7 +44,10 @@ impl HTMLPrinter {
         match kind {
             TokenKind::X => Some("x"),
             TokenKind::Ident => Some("ident"),
+            TokenKind::Label => Some("label"),
+            TokenKind::Comment => Some("comment"),
             TokenKind::Bodyweight => Some("bw"),
+            TokenKind::Kilogram | TokenKind::Pound => Some("unit"),
             TokenKind::Error => Some("error"),
             _ => None,
         }
@@ -52,8 +55,17 @@ impl HTMLPrinter {
 
     fn node_tag(kind: NodeKind) -> Option<&'static str> {
         match kind {
+            NodeKind::Header => Some("header"),
+            NodeKind::Session => Some("session"),
+            NodeKind::Repeat => Some("repeat"),
+            NodeKind::Superset => Some("superset"),
             NodeKind::SetGroup => Some("set-group"),
             NodeKind::Exercise => Some("exercise"),
+            NodeKind::Modifiers => Some("modifiers"),
+            NodeKind::Tags => Some("tags"),
+            NodeKind::Effort => Some("effort"),
+            NodeKind::Note => Some("note"),
+            NodeKind::Tempo => Some("tempo"),
             NodeKind::Error => Some("error"),
             _ => None,
         }
@@ -114,7 +126,7 @@ impl WorkoutCst {
     }
 
     #[wasm_bindgen(js_name = toString)]
-    pub fn to_string(&self) -> String {
+    pub fn debug_string(&self) -> String {
         let source_tree = SourceTree::new(&self.tree);
         format!("{source_tree:#?}")
     }
@@ -136,7 +148,7 @@ impl WorkoutCst {
     }
 
     #[wasm_bindgen(js_name = lookupOffset)]
-    pub fn lookup_offset(&self, offset: u32) -> Option<JSTokenContext> {
+    pub fn lookup_offset(&self, _offset: u32) -> Option<JSTokenContext> {
         // self.tree
         //     .root()
         //     .lookup_offset(offset, &self.tree)
@@ -146,20 +158,33 @@ impl WorkoutCst {
 }
 
 #[wasm_bindgen]
-pub struct WorkoutHir(hir::Workout);
+pub struct WorkoutHir {
+    hir: hir::Workout,
+    errors: Vec<hir::LowerError>,
+}
 
 #[wasm_bindgen]
 impl WorkoutHir {
     #[wasm_bindgen(constructor)]
     pub fn new(cst: &WorkoutCst) -> Self {
         let workout = Workout::cast(cst.tree.root(), &cst.tree).unwrap();
-        let hir = hir::Workout::lower(workout, &cst.tree);
-        Self(hir)
+        let (hir, errors) = hir::Workout::lower(workout, &cst.tree);
+
+        Self { hir, errors }
     }
 
     #[wasm_bindgen(js_name = toString)]
-    pub fn to_string(&self) -> String {
-        format!("{:#?}", self.0)
+    pub fn debug_string(&self) -> String {
+        format!("{:#?}", self.hir)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn errors(&self) -> Vec<JsValue> {
+        self.errors
+            .iter()
+            .map(JSLowerError::from)
+            .map(|e| serde_wasm_bindgen::to_value(&e).unwrap())
+            .collect()
     }
 }
 
@@ -3,8 +3,9 @@ use tsify::Tsify;
 use wasm_bindgen::prelude::*;
 
 use wlang::{
-    ast::{walker::TokenContext, NodeKind},
+    ast::NodeKind,
     autocomplete::CompletionTrie,
+    hir::{LowerError, LowerErrorKind},
     lexer::{Token, TokenKind},
     parser::ParseError,
 };
@@ -15,13 +16,45 @@ use wlang::{
 pub enum JSNodeKind {
     Error,
     Workout,
+    Header,
+    HeaderEntry,
+    HeaderKey,
+    HeaderValue,
+    Session,
+    SessionDate,
+    SessionTitle,
+    Superset,
     Exercise,
     SetGroup,
+    TrainingMax,
     Set,
+    SetCount,
+    Effort,
+    Note,
+    Tempo,
+    DropSet,
     Weight,
     Reps,
     SimpleDuration,
     LongDuration,
+    Distance,
+    RepRange,
+    RestPause,
+    Let,
+    Name,
+    NameRef,
+    Literal,
+    BinaryExpr,
+    ParenExpr,
+    Repeat,
+    Include,
+    Modifiers,
+    Modifier,
+    Tags,
+    Tag,
+    CardioSet,
+    Pace,
+    Calories,
 }
 
 impl From<NodeKind> for JSNodeKind {
@@ -29,13 +62,45 @@ impl From<NodeKind> for JSNodeKind {
         match value {
             NodeKind::Error => Self::Error,
             NodeKind::Workout => Self::Workout,
+            NodeKind::Header => Self::Header,
+            NodeKind::HeaderEntry => Self::HeaderEntry,
+            NodeKind::HeaderKey => Self::HeaderKey,
+            NodeKind::HeaderValue => Self::HeaderValue,
+            NodeKind::Session => Self::Session,
+            NodeKind::SessionDate => Self::SessionDate,
+            NodeKind::SessionTitle => Self::SessionTitle,
+            NodeKind::Superset => Self::Superset,
             NodeKind::Exercise => Self::Exercise,
             NodeKind::SetGroup => Self::SetGroup,
+            NodeKind::TrainingMax => Self::TrainingMax,
             NodeKind::Set => Self::Set,
+            NodeKind::SetCount => Self::SetCount,
+            NodeKind::Effort => Self::Effort,
+            NodeKind::Note => Self::Note,
+            NodeKind::Tempo => Self::Tempo,
+            NodeKind::DropSet => Self::DropSet,
             NodeKind::Weight => Self::Weight,
             NodeKind::Reps => Self::Reps,
             NodeKind::SimpleDuration => Self::SimpleDuration,
             NodeKind::LongDuration => Self::LongDuration,
+            NodeKind::Distance => Self::Distance,
+            NodeKind::RepRange => Self::RepRange,
+            NodeKind::RestPause => Self::RestPause,
+            NodeKind::Let => Self::Let,
+            NodeKind::Name => Self::Name,
+            NodeKind::NameRef => Self::NameRef,
+            NodeKind::Literal => Self::Literal,
+            NodeKind::BinaryExpr => Self::BinaryExpr,
+            NodeKind::ParenExpr => Self::ParenExpr,
+            NodeKind::Repeat => Self::Repeat,
+            NodeKind::Include => Self::Include,
+            NodeKind::Modifiers => Self::Modifiers,
+            NodeKind::Modifier => Self::Modifier,
+            NodeKind::Tags => Self::Tags,
+            NodeKind::Tag => Self::Tag,
+            NodeKind::CardioSet => Self::CardioSet,
+            NodeKind::Pace => Self::Pace,
+            NodeKind::Calories => Self::Calories,
         }
     }
 }
@@ -44,19 +109,57 @@ impl From<NodeKind> for JSNodeKind {
 #[tsify(into_wasm_abi, from_wasm_abi)]
 #[serde(rename_all = "snake_case")]
 pub enum JSTokenKind {
+    Warmup,
     Bodyweight,
     X,
+    Amrap,
+    Fail,
     Plus,
+    Minus,
+    Greater,
+    Star,
+    Slash,
+    PerSide,
+    At,
+    Rpe,
+    Rir,
+    Kilogram,
+    Pound,
+    Percent,
+    Tm,
     Integer,
     Float,
     Hour,
     Minute,
     Second,
+    Meter,
+    Kilometer,
+    Yard,
+    Foot,
+    Mile,
+    Calorie,
+    HeartRate,
     Colon,
+    Equals,
+    LParen,
+    RParen,
+    Let,
+    Repeat,
+    LBrace,
+    RBrace,
+    LBracket,
+    RBracket,
+    Include,
+    Text,
+    Tempo,
     Hash,
+    DoubleHash,
+    Label,
+    Fence,
     Comma,
     Newline,
     Space,
+    Comment,
     Ident,
     Eof,
     Error,
@@ -65,19 +168,57 @@ pub enum JSTokenKind {
 impl From<TokenKind> for JSTokenKind {
     fn from(value: TokenKind) -> Self {
         match value {
+            TokenKind::Warmup => Self::Warmup,
             TokenKind::Bodyweight => Self::Bodyweight,
             TokenKind::X => Self::X,
+            TokenKind::Amrap => Self::Amrap,
+            TokenKind::Fail => Self::Fail,
             TokenKind::Plus => Self::Plus,
+            TokenKind::Minus => Self::Minus,
+            TokenKind::Greater => Self::Greater,
+            TokenKind::Star => Self::Star,
+            TokenKind::Slash => Self::Slash,
+            TokenKind::PerSide => Self::PerSide,
+            TokenKind::At => Self::At,
+            TokenKind::Rpe => Self::Rpe,
+            TokenKind::Rir => Self::Rir,
+            TokenKind::Kilogram => Self::Kilogram,
+            TokenKind::Pound => Self::Pound,
+            TokenKind::Percent => Self::Percent,
+            TokenKind::Tm => Self::Tm,
             TokenKind::Integer => Self::Integer,
             TokenKind::Float => Self::Float,
             TokenKind::Hour => Self::Hour,
             TokenKind::Minute => Self::Minute,
             TokenKind::Second => Self::Second,
+            TokenKind::Meter => Self::Meter,
+            TokenKind::Kilometer => Self::Kilometer,
+            TokenKind::Yard => Self::Yard,
+            TokenKind::Foot => Self::Foot,
+            TokenKind::Mile => Self::Mile,
+            TokenKind::Calorie => Self::Calorie,
+            TokenKind::HeartRate => Self::HeartRate,
             TokenKind::Colon => Self::Colon,
+            TokenKind::Equals => Self::Equals,
+            TokenKind::LParen => Self::LParen,
+            TokenKind::RParen => Self::RParen,
+            TokenKind::Let => Self::Let,
+            TokenKind::Repeat => Self::Repeat,
+            TokenKind::LBrace => Self::LBrace,
+            TokenKind::RBrace => Self::RBrace,
+            TokenKind::LBracket => Self::LBracket,
+            TokenKind::RBracket => Self::RBracket,
+            TokenKind::Include => Self::Include,
+            TokenKind::Text => Self::Text,
+            TokenKind::Tempo => Self::Tempo,
             TokenKind::Hash => Self::Hash,
+            TokenKind::DoubleHash => Self::DoubleHash,
+            TokenKind::Label => Self::Label,
+            TokenKind::Fence => Self::Fence,
             TokenKind::Comma => Self::Comma,
             TokenKind::Newline => Self::Newline,
             TokenKind::Space => Self::Space,
+            TokenKind::Comment => Self::Comment,
             TokenKind::Ident => Self::Ident,
             TokenKind::Eof => Self::Eof,
             TokenKind::Error => Self::Error,
@@ -110,6 +251,71 @@ pub struct JSTokenContext {
     pub token: JSToken,
 }
 
+#[derive(Debug, Clone, Copy, Tsify, Serialize, Deserialize)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+#[serde(rename_all = "snake_case")]
+pub enum JSLowerErrorKind {
+    InvalidRpe,
+    InvalidRir,
+    InvalidTrainingMax,
+    MissingTrainingMax,
+    InvalidDate,
+    InvalidBodyweight,
+    InvalidUnit,
+    UndefinedName,
+    DivisionByZero,
+    InvalidRepeatCount,
+    InvalidLabel,
+    InvalidRange,
+    InvalidHeartRate,
+    InvalidSetCount,
+    InvalidReps,
+    InvalidDuration,
+    InvalidCalories,
+}
+
+impl From<&LowerErrorKind> for JSLowerErrorKind {
+    fn from(value: &LowerErrorKind) -> Self {
+        match value {
+            LowerErrorKind::InvalidRpe => Self::InvalidRpe,
+            LowerErrorKind::InvalidRir => Self::InvalidRir,
+            LowerErrorKind::InvalidTrainingMax => Self::InvalidTrainingMax,
+            LowerErrorKind::MissingTrainingMax => Self::MissingTrainingMax,
+            LowerErrorKind::InvalidDate => Self::InvalidDate,
+            LowerErrorKind::InvalidBodyweight => Self::InvalidBodyweight,
+            LowerErrorKind::InvalidUnit => Self::InvalidUnit,
+            LowerErrorKind::UndefinedName => Self::UndefinedName,
+            LowerErrorKind::DivisionByZero => Self::DivisionByZero,
+            LowerErrorKind::InvalidRepeatCount => Self::InvalidRepeatCount,
+            LowerErrorKind::InvalidLabel => Self::InvalidLabel,
+            LowerErrorKind::InvalidRange => Self::InvalidRange,
+            LowerErrorKind::InvalidHeartRate => Self::InvalidHeartRate,
+            LowerErrorKind::InvalidSetCount => Self::InvalidSetCount,
+            LowerErrorKind::InvalidReps => Self::InvalidReps,
+            LowerErrorKind::InvalidDuration => Self::InvalidDuration,
+            LowerErrorKind::InvalidCalories => Self::InvalidCalories,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Tsify, Serialize, Deserialize)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct JSLowerError {
+    pub kind: JSLowerErrorKind,
+    pub start: u32,
+    pub end: u32,
+}
+
+impl From<&LowerError> for JSLowerError {
+    fn from(value: &LowerError) -> Self {
+        Self {
+            kind: value.kind().into(),
+            start: value.range().start().into(),
+            end: value.range().end().into(),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct JSParseError(ParseError);
 
@@ -12,13 +12,45 @@ pub mod walker;
 pub enum NodeKind {
     Error,
     Workout,
+    Header,
+    HeaderEntry,
+    HeaderKey,
+    HeaderValue,
+    Session,
+    SessionDate,
+    SessionTitle,
+    Superset,
     Exercise,
     SetGroup,
+    TrainingMax,
     Set,
+    SetCount,
+    Effort,
+    Note,
+    Tempo,
+    DropSet,
     Weight,
     Reps,
     SimpleDuration,
     LongDuration,
+    Distance,
+    RepRange,
+    RestPause,
+    Let,
+    Name,
+    NameRef,
+    Literal,
+    BinaryExpr,
+    ParenExpr,
+    Repeat,
+    Include,
+    Modifiers,
+    Modifier,
+    Tags,
+    Tag,
+    CardioSet,
+    Pace,
+    Calories,
 }
 
 unsafe impl eventree::SyntaxKind for NodeKind {
@@ -161,9 +193,135 @@ fn find_child_token<A: AstToken>(node: &SyntaxNode, tree: &SyntaxTree) -> Option
 impl_ast_node!(NodeKind::Workout);
 
 impl Workout {
+    pub fn header(&self, tree: &SyntaxTree) -> Option<Header> {
+        find_child_node(&self.0, tree)
+    }
+
+    /// top level set groups and supersets in order
+    pub fn blocks<'t>(&self, tree: &'t SyntaxTree) -> impl Iterator<Item = Block> + 't {
+        child_nodes(&self.0, tree)
+    }
+
+    /// every set group in the workout, including those within supersets and sessions
+    pub fn set_groups<'t>(&self, tree: &'t SyntaxTree) -> impl Iterator<Item = SetGroup> + 't {
+        self.0
+            .descendant_nodes(tree)
+            .filter_map(|n| SetGroup::cast(n, tree))
+    }
+
+    /// `let` bindings outside of any session or set group
+    pub fn lets<'t>(&self, tree: &'t SyntaxTree) -> impl Iterator<Item = Let> + 't {
+        child_nodes(&self.0, tree)
+    }
+
+    /// sessions started by `##` headings
+    pub fn sessions<'t>(&self, tree: &'t SyntaxTree) -> impl Iterator<Item = Session> + 't {
+        child_nodes(&self.0, tree)
+    }
+
+    fn header_value(&self, tree: &SyntaxTree, key: &str) -> Option<HeaderValue> {
+        self.header(tree).and_then(|h| h.get(tree, key))
+    }
+
+    pub fn date(&self, tree: &SyntaxTree) -> Option<HeaderValue> {
+        self.header_value(tree, "date")
+    }
+
+    pub fn title(&self, tree: &SyntaxTree) -> Option<HeaderValue> {
+        self.header_value(tree, "title")
+    }
+
+    pub fn bodyweight(&self, tree: &SyntaxTree) -> Option<HeaderValue> {
+        self.header_value(tree, "bodyweight")
+    }
+
+    pub fn location(&self, tree: &SyntaxTree) -> Option<HeaderValue> {
+        self.header_value(tree, "location")
+    }
+
+    /// default unit for weights in the document
+    pub fn unit(&self, tree: &SyntaxTree) -> Option<HeaderValue> {
+        self.header_value(tree, "unit")
+    }
+}
+
+impl_ast_node!(NodeKind::Session);
+
+impl Session {
+    pub fn date(&self, tree: &SyntaxTree) -> Option<SessionDate> {
+        find_child_node(&self.0, tree)
+    }
+
+    pub fn title(&self, tree: &SyntaxTree) -> Option<SessionTitle> {
+        find_child_node(&self.0, tree)
+    }
+
+    /// ex `[legs]` of `## 2026-10-17 Lower [legs]`
+    pub fn tags(&self, tree: &SyntaxTree) -> Option<Tags> {
+        find_child_node(&self.0, tree)
+    }
+
+    pub fn blocks<'t>(&self, tree: &'t SyntaxTree) -> impl Iterator<Item = Block> + 't {
+        child_nodes(&self.0, tree)
+    }
+
+    /// `let` bindings outside of any set group
+    pub fn lets<'t>(&self, tree: &'t SyntaxTree) -> impl Iterator<Item = Let> + 't {
+        child_nodes(&self.0, tree)
+    }
+
+    /// every set group in the session, including those within supersets
     pub fn set_groups<'t>(&self, tree: &'t SyntaxTree) -> impl Iterator<Item = SetGroup> + 't {
+        self.0
+            .descendant_nodes(tree)
+            .filter_map(|n| SetGroup::cast(n, tree))
+    }
+}
+
+impl_ast_node!(NodeKind::SessionDate);
+impl_ast_node!(NodeKind::SessionTitle);
+
+impl_ast_node!(NodeKind::Header);
+
+impl Header {
+    pub fn entries<'t>(&self, tree: &'t SyntaxTree) -> impl Iterator<Item = HeaderEntry> + 't {
         child_nodes(&self.0, tree)
     }
+
+    /// value of the first entry with a key matching `key` ignoring case
+    pub fn get(&self, tree: &SyntaxTree, key: &str) -> Option<HeaderValue> {
+        self.entries(tree)
+            .find(|e| {
+                e.key(tree)
+                    .is_some_and(|k| k.text(tree).trim().eq_ignore_ascii_case(key))
+            })
+            .and_then(|e| e.value(tree))
+    }
+}
+
+impl_ast_node!(NodeKind::HeaderEntry);
+
+impl HeaderEntry {
+    pub fn key(&self, tree: &SyntaxTree) -> Option<HeaderKey> {
+        find_child_node(&self.0, tree)
+    }
+
+    pub fn value(&self, tree: &SyntaxTree) -> Option<HeaderValue> {
+        find_child_node(&self.0, tree)
+    }
+}
+
+impl_ast_node!(NodeKind::HeaderKey);
+impl_ast_node!(NodeKind::HeaderValue);
+
+impl HeaderValue {
+    pub fn number(&self, tree: &SyntaxTree) -> Option<WeightLiteral> {
+        find_child_token(&self.0, tree)
+    }
+
+    pub fn unit(&self, tree: &SyntaxTree) -> Option<WeightUnit> {
+        find_child_token(&self.0, tree)
+    }
 }
 
 //     pub fn walk<W: TreeWalker>(&self, walker: &mut W, source: &str) -> Result<(), W::Err> {
@@ -171,9 +329,130 @@ impl Workout {
 //     }
 // }
 
+pub enum Block {
+    SetGroup(SetGroup),
+    Superset(Superset),
+    Repeat(Repeat),
+    Include(Include),
+}
+
+impl AstNode for Block {
+    fn cast(node: SyntaxNode, tree: &SyntaxTree) -> Option<Self> {
+        match node.kind(tree) {
+            NodeKind::SetGroup => Some(Self::SetGroup(SetGroup(node))),
+            NodeKind::Superset => Some(Self::Superset(Superset(node))),
+            NodeKind::Repeat => Some(Self::Repeat(Repeat(node))),
+            NodeKind::Include => Some(Self::Include(Include(node))),
+            _ => None,
+        }
+    }
+
+    fn range(&self, tree: &SyntaxTree) -> TextRange {
+        match self {
+            Block::SetGroup(set_group) => set_group.range(tree),
+            Block::Superset(superset) => superset.range(tree),
+            Block::Repeat(repeat) => repeat.range(tree),
+            Block::Include(include) => include.range(tree),
+        }
+    }
+
+    fn text<'t>(&self, tree: &'t SyntaxTree) -> &'t str {
+        match self {
+            Block::SetGroup(set_group) => set_group.text(tree),
+            Block::Superset(superset) => superset.text(tree),
+            Block::Repeat(repeat) => repeat.text(tree),
+            Block::Include(include) => include.text(tree),
+        }
+    }
+}
+
+impl_ast_node!(NodeKind::Include);
+
+impl Include {
+    /// quoted path of the included document, ex `"warmup.wl"`
+    pub fn path(&self, tree: &SyntaxTree) -> Option<Text> {
+        find_child_token(&self.0, tree)
+    }
+}
+
+impl_ast_node!(NodeKind::Repeat);
+
+impl Repeat {
+    /// times the body is done, ex `3` of `repeat 3 {`
+    pub fn count(&self, tree: &SyntaxTree) -> Option<Integer> {
+        find_child_token(&self.0, tree)
+    }
+
+    /// set groups and supersets when repeating around set groups
+    pub fn blocks<'t>(&self, tree: &'t SyntaxTree) -> impl Iterator<Item = Block> + 't {
+        child_nodes(&self.0, tree)
+    }
+
+    /// sets and nested repeats when repeating within a set group
+    pub fn items<'t>(&self, tree: &'t SyntaxTree) -> impl Iterator<Item = SetItem> + 't {
+        child_nodes(&self.0, tree)
+    }
+
+    pub fn lets<'t>(&self, tree: &'t SyntaxTree) -> impl Iterator<Item = Let> + 't {
+        child_nodes(&self.0, tree)
+    }
+}
+
+pub enum SetItem {
+    Set(Set),
+    Cardio(CardioSet),
+    Repeat(Repeat),
+}
+
+impl AstNode for SetItem {
+    fn cast(node: SyntaxNode, tree: &SyntaxTree) -> Option<Self> {
+        match node.kind(tree) {
+            NodeKind::Set => Some(Self::Set(Set(node))),
+            NodeKind::CardioSet => Some(Self::Cardio(CardioSet(node))),
+            NodeKind::Repeat => Some(Self::Repeat(Repeat(node))),
+            _ => None,
+        }
+    }
+
+    fn range(&self, tree: &SyntaxTree) -> TextRange {
+        match self {
+            SetItem::Set(set) => set.range(tree),
+            SetItem::Cardio(cardio) => cardio.range(tree),
+            SetItem::Repeat(repeat) => repeat.range(tree),
+        }
+    }
+
+    fn text<'t>(&self, tree: &'t SyntaxTree) -> &'t str {
+        match self {
+            SetItem::Set(set) => set.text(tree),
+            SetItem::Cardio(cardio) => cardio.text(tree),
+            SetItem::Repeat(repeat) => repeat.text(tree),
+        }
+    }
+}
+
+impl_ast_node!(NodeKind::Superset);
+
+impl Superset {
+    pub fn set_groups<'t>(&self, tree: &'t SyntaxTree) -> impl Iterator<Item = SetGroup> + 't {
+        child_nodes(&self.0, tree)
+    }
+
+    /// letter shared by the labels of the set groups
+    pub fn letter(&self, tree: &SyntaxTree) -> Option<char> {
+        self.set_groups(tree)
+            .find_map(|sg| sg.label(tree))
+            .map(|l| l.letter(tree))
+    }
+}
+
 impl_ast_node!(NodeKind::SetGroup);
 
 impl SetGroup {
+    pub fn label(&self, tree: &SyntaxTree) -> Option<Label> {
+        find_child_token(&self.0, tree)
+    }
+
     pub fn exercise(&self, tree: &SyntaxTree) -> Option<Exercise> {
         find_child_node(&self.0, tree)
     }
@@ -181,6 +460,32 @@ impl SetGroup {
     pub fn sets<'t>(&self, tree: &'t SyntaxTree) -> impl Iterator<Item = Set> + 't {
         child_nodes(&self.0, tree)
     }
+
+    /// sets and repeats of sets in order
+    pub fn items<'t>(&self, tree: &'t SyntaxTree) -> impl Iterator<Item = SetItem> + 't {
+        child_nodes(&self.0, tree)
+    }
+
+    pub fn training_max(&self, tree: &SyntaxTree) -> Option<TrainingMax> {
+        find_child_node(&self.0, tree)
+    }
+
+    pub fn lets<'t>(&self, tree: &'t SyntaxTree) -> impl Iterator<Item = Let> + 't {
+        child_nodes(&self.0, tree)
+    }
+
+    /// note trailing the exercise header
+    pub fn note(&self, tree: &SyntaxTree) -> Option<Note> {
+        find_child_node(&self.0, tree)
+    }
+}
+
+impl_ast_node!(NodeKind::TrainingMax);
+
+impl TrainingMax {
+    pub fn weight(&self, tree: &SyntaxTree) -> Option<Weight> {
+        find_child_node(&self.0, tree)
+    }
 }
 
 impl_ast_node!(NodeKind::Exercise);
@@ -189,6 +494,55 @@ impl Exercise {
     pub fn ident(&self, tree: &SyntaxTree) -> Option<Ident> {
         find_child_token(&self.0, tree)
     }
+
+    /// movement without its modifiers, ex `Squat` of `# Squat (paused, belt)`
+    pub fn base_name<'t>(&self, tree: &'t SyntaxTree) -> Option<&'t str> {
+        self.ident(tree).map(|i| i.text(tree))
+    }
+
+    /// ex `(paused, belt)` of `# Squat (paused, belt)`
+    pub fn modifiers(&self, tree: &SyntaxTree) -> Option<Modifiers> {
+        find_child_node(&self.0, tree)
+    }
+
+    /// ex `[heavy, comp]` of `# Squat [heavy, comp]`
+    pub fn tags(&self, tree: &SyntaxTree) -> Option<Tags> {
+        find_child_node(&self.0, tree)
+    }
+}
+
+impl_ast_node!(NodeKind::Modifiers);
+
+impl Modifiers {
+    pub fn modifiers<'t>(&self, tree: &'t SyntaxTree) -> impl Iterator<Item = Modifier> + 't {
+        child_nodes(&self.0, tree)
+    }
+}
+
+impl_ast_node!(NodeKind::Modifier);
+
+impl Modifier {
+    /// text of the modifier without surrounding spaces
+    pub fn name<'t>(&self, tree: &'t SyntaxTree) -> &'t str {
+        self.text(tree).trim()
+    }
+}
+
+impl_ast_node!(NodeKind::Tags);
+
+impl Tags {
+    pub fn tags<'t>(&self, tree: &'t SyntaxTree) -> impl Iterator<Item = Tag> + 't {
+        child_nodes(&self.0, tree)
+    }
+}
+
+impl_ast_node!(NodeKind::Tag);
+
+impl Tag {
+    /// text of the tag without surrounding spaces
+    pub fn name<'t>(&self, tree: &'t SyntaxTree) -> &'t str {
+        self.text(tree).trim()
+    }
 }
 
 impl_ast_node!(NodeKind::Set);
@@ -201,6 +555,140 @@ impl Set {
     pub fn quantity(&self, tree: &SyntaxTree) -> Option<Quantity> {
         find_child_node(&self.0, tree)
     }
+
+    pub fn set_count(&self, tree: &SyntaxTree) -> Option<SetCount> {
+        find_child_node(&self.0, tree)
+    }
+
+    pub fn effort(&self, tree: &SyntaxTree) -> Option<Effort> {
+        find_child_node(&self.0, tree)
+    }
+
+    pub fn tempo(&self, tree: &SyntaxTree) -> Option<Tempo> {
+        find_child_node(&self.0, tree)
+    }
+
+    pub fn note(&self, tree: &SyntaxTree) -> Option<Note> {
+        find_child_node(&self.0, tree)
+    }
+
+    /// marked as a warm-up, ex `w 135x5` or `(w) 135x5`
+    pub fn is_warmup(&self, tree: &SyntaxTree) -> bool {
+        self.0
+            .child_tokens(tree)
+            .any(|t| t.kind(tree) == TokenKind::Warmup)
+    }
+
+    /// next set of the chain, ex `185x6` of `225x8 > 185x6`
+    pub fn drop_set(&self, tree: &SyntaxTree) -> Option<DropSet> {
+        find_child_node(&self.0, tree)
+    }
+}
+
+impl_ast_node!(NodeKind::DropSet);
+
+impl DropSet {
+    pub fn set(&self, tree: &SyntaxTree) -> Option<Set> {
+        find_child_node(&self.0, tree)
+    }
+}
+
+impl_ast_node!(NodeKind::Tempo);
+
+impl Tempo {
+    /// eccentric, bottom, concentric and top phases
+    pub fn phases(&self, tree: &SyntaxTree) -> Option<[TempoPhase; 4]> {
+        let text = self.text(tree);
+        let digits = text
+            .strip_prefix(['t', 'T'])
+            .and_then(|t| t.strip_prefix(':'))
+            .unwrap_or(text);
+
+        let mut phases = digits.chars().filter(|&c| c != '-').map(|c| match c {
+            'x' | 'X' => TempoPhase::Explosive,
+            c => TempoPhase::Seconds(c.to_digit(10).unwrap() as usize),
+        });
+
+        Some([
+            phases.next()?,
+            phases.next()?,
+            phases.next()?,
+            phases.next()?,
+        ])
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TempoPhase {
+    Seconds(usize),
+    /// `X`, move as fast as possible
+    Explosive,
+}
+
+impl_ast_node!(NodeKind::Note);
+
+impl Note {
+    pub fn content(&self, tree: &SyntaxTree) -> Option<Text> {
+        find_child_token(&self.0, tree)
+    }
+}
+
+impl_ast_node!(NodeKind::Effort);
+
+impl Effort {
+    pub fn scale(&self, tree: &SyntaxTree) -> Option<EffortScale> {
+        find_child_token(&self.0, tree)
+    }
+
+    pub fn value(&self, tree: &SyntaxTree) -> Option<WeightLiteral> {
+        find_child_token(&self.0, tree)
+    }
+}
+
+/// How an effort was rated, `@` is shorthand for rpe
+pub enum EffortScale {
+    At(At),
+    Rpe(Rpe),
+    Rir(Rir),
+}
+
+impl AstToken for EffortScale {
+    fn cast(token: SyntaxToken, tree: &SyntaxTree) -> Option<Self> {
+        match token.kind(tree) {
+            TokenKind::At => Some(Self::At(At(token))),
+            TokenKind::Rpe => Some(Self::Rpe(Rpe(token))),
+            TokenKind::Rir => Some(Self::Rir(Rir(token))),
+            _ => None,
+        }
+    }
+
+    fn range(&self, tree: &SyntaxTree) -> TextRange {
+        match self {
+            EffortScale::At(at) => at.range(tree),
+            EffortScale::Rpe(rpe) => rpe.range(tree),
+            EffortScale::Rir(rir) => rir.range(tree),
+        }
+    }
+
+    fn text<'t>(&self, tree: &'t SyntaxTree) -> &'t str {
+        match self {
+            EffortScale::At(at) => at.text(tree),
+            EffortScale::Rpe(rpe) => rpe.text(tree),
+            EffortScale::Rir(rir) => rir.text(tree),
+        }
+    }
+}
+
+impl_ast_token!(TokenKind::At);
+impl_ast_token!(TokenKind::Rpe);
+impl_ast_token!(TokenKind::Rir);
+
+impl_ast_node!(NodeKind::SetCount);
+
+impl SetCount {
+    pub fn count(&self, tree: &SyntaxTree) -> Option<Integer> {
+        find_child_token(&self.0, tree)
+    }
 }
 
 impl_ast_node!(NodeKind::Weight);
@@ -213,8 +701,227 @@ impl Weight {
     pub fn bodyweight(&self, tree: &SyntaxTree) -> Option<Bodyweight> {
         find_child_token(&self.0, tree)
     }
+
+    pub fn unit(&self, tree: &SyntaxTree) -> Option<WeightUnit> {
+        find_child_token(&self.0, tree)
+    }
+
+    /// `%` marking the weight as a percentage of training max
+    pub fn percent(&self, tree: &SyntaxTree) -> Option<Percent> {
+        find_child_token(&self.0, tree)
+    }
+
+    /// computed weight, ex `0.65*tm` or `tm - 20`
+    pub fn expr(&self, tree: &SyntaxTree) -> Option<Expr> {
+        find_child_node(&self.0, tree)
+    }
+
+    /// `+` or `-` of `bw + 45` or `bw - 30`
+    pub fn operator(&self, tree: &SyntaxTree) -> Option<WeightOperator> {
+        find_child_token(&self.0, tree)
+    }
+
+    /// load added to or removed from bodyweight, ex `30` of `bw - 30`
+    pub fn modifier(&self, tree: &SyntaxTree) -> Option<WeightLiteral> {
+        self.bodyweight(tree)?;
+        self.weight(tree)
+    }
+}
+
+pub enum WeightOperator {
+    Plus(Plus),
+    Minus(Minus),
+}
+
+impl AstToken for WeightOperator {
+    fn cast(token: SyntaxToken, tree: &SyntaxTree) -> Option<Self> {
+        match token.kind(tree) {
+            TokenKind::Plus => Some(Self::Plus(Plus(token))),
+            TokenKind::Minus => Some(Self::Minus(Minus(token))),
+            _ => None,
+        }
+    }
+
+    fn range(&self, tree: &SyntaxTree) -> TextRange {
+        match self {
+            WeightOperator::Plus(plus) => plus.range(tree),
+            WeightOperator::Minus(minus) => minus.range(tree),
+        }
+    }
+
+    fn text<'t>(&self, tree: &'t SyntaxTree) -> &'t str {
+        match self {
+            WeightOperator::Plus(plus) => plus.text(tree),
+            WeightOperator::Minus(minus) => minus.text(tree),
+        }
+    }
+}
+
+impl_ast_token!(TokenKind::Plus);
+impl_ast_token!(TokenKind::Minus);
+impl_ast_token!(TokenKind::Star);
+impl_ast_token!(TokenKind::Slash);
+
+impl_ast_node!(NodeKind::Let);
+
+impl Let {
+    pub fn name(&self, tree: &SyntaxTree) -> Option<Name> {
+        find_child_node(&self.0, tree)
+    }
+
+    pub fn value(&self, tree: &SyntaxTree) -> Option<Expr> {
+        find_child_node(&self.0, tree)
+    }
+
+    /// ex `kg` of `let tm = 140kg`, `None` for a plain number
+    pub fn unit(&self, tree: &SyntaxTree) -> Option<WeightUnit> {
+        find_child_token(&self.0, tree)
+    }
+}
+
+impl_ast_node!(NodeKind::Name);
+impl_ast_node!(NodeKind::NameRef);
+
+pub enum Expr {
+    Literal(Literal),
+    NameRef(NameRef),
+    Binary(BinaryExpr),
+    Paren(ParenExpr),
+}
+
+impl AstNode for Expr {
+    fn cast(node: SyntaxNode, tree: &SyntaxTree) -> Option<Self> {
+        match node.kind(tree) {
+            NodeKind::Literal => Some(Self::Literal(Literal(node))),
+            NodeKind::NameRef => Some(Self::NameRef(NameRef(node))),
+            NodeKind::BinaryExpr => Some(Self::Binary(BinaryExpr(node))),
+            NodeKind::ParenExpr => Some(Self::Paren(ParenExpr(node))),
+            _ => None,
+        }
+    }
+
+    fn range(&self, tree: &SyntaxTree) -> TextRange {
+        match self {
+            Expr::Literal(literal) => literal.range(tree),
+            Expr::NameRef(name) => name.range(tree),
+            Expr::Binary(binary) => binary.range(tree),
+            Expr::Paren(paren) => paren.range(tree),
+        }
+    }
+
+    fn text<'t>(&self, tree: &'t SyntaxTree) -> &'t str {
+        match self {
+            Expr::Literal(literal) => literal.text(tree),
+            Expr::NameRef(name) => name.text(tree),
+            Expr::Binary(binary) => binary.text(tree),
+            Expr::Paren(paren) => paren.text(tree),
+        }
+    }
 }
 
+impl_ast_node!(NodeKind::Literal);
+
+impl Literal {
+    pub fn value(&self, tree: &SyntaxTree) -> Option<WeightLiteral> {
+        find_child_token(&self.0, tree)
+    }
+}
+
+impl_ast_node!(NodeKind::BinaryExpr);
+
+impl BinaryExpr {
+    pub fn lhs(&self, tree: &SyntaxTree) -> Option<Expr> {
+        child_nodes(&self.0, tree).next()
+    }
+
+    pub fn rhs(&self, tree: &SyntaxTree) -> Option<Expr> {
+        child_nodes(&self.0, tree).nth(1)
+    }
+
+    pub fn op(&self, tree: &SyntaxTree) -> Option<BinaryOp> {
+        find_child_token(&self.0, tree)
+    }
+}
+
+impl_ast_node!(NodeKind::ParenExpr);
+
+impl ParenExpr {
+    pub fn expr(&self, tree: &SyntaxTree) -> Option<Expr> {
+        find_child_node(&self.0, tree)
+    }
+}
+
+pub enum BinaryOp {
+    Plus(Plus),
+    Minus(Minus),
+    Star(Star),
+    Slash(Slash),
+}
+
+impl AstToken for BinaryOp {
+    fn cast(token: SyntaxToken, tree: &SyntaxTree) -> Option<Self> {
+        match token.kind(tree) {
+            TokenKind::Plus => Some(Self::Plus(Plus(token))),
+            TokenKind::Minus => Some(Self::Minus(Minus(token))),
+            TokenKind::Star => Some(Self::Star(Star(token))),
+            TokenKind::Slash => Some(Self::Slash(Slash(token))),
+            _ => None,
+        }
+    }
+
+    fn range(&self, tree: &SyntaxTree) -> TextRange {
+        match self {
+            BinaryOp::Plus(plus) => plus.range(tree),
+            BinaryOp::Minus(minus) => minus.range(tree),
+            BinaryOp::Star(star) => star.range(tree),
+            BinaryOp::Slash(slash) => slash.range(tree),
+        }
+    }
+
+    fn text<'t>(&self, tree: &'t SyntaxTree) -> &'t str {
+        match self {
+            BinaryOp::Plus(plus) => plus.text(tree),
+            BinaryOp::Minus(minus) => minus.text(tree),
+            BinaryOp::Star(star) => star.text(tree),
+            BinaryOp::Slash(slash) => slash.text(tree),
+        }
+    }
+}
+
+impl_ast_token!(TokenKind::Percent);
+
+pub enum WeightUnit {
+    Kilogram(Kilogram),
+    Pound(Pound),
+}
+
+impl AstToken for WeightUnit {
+    fn cast(token: SyntaxToken, tree: &SyntaxTree) -> Option<Self> {
+        match token.kind(tree) {
+            TokenKind::Kilogram => Some(Self::Kilogram(Kilogram(token))),
+            TokenKind::Pound => Some(Self::Pound(Pound(token))),
+            _ => None,
+        }
+    }
+
+    fn range(&self, tree: &SyntaxTree) -> TextRange {
+        match self {
+            WeightUnit::Kilogram(kilogram) => kilogram.range(tree),
+            WeightUnit::Pound(pound) => pound.range(tree),
+        }
+    }
+
+    fn text<'t>(&self, tree: &'t SyntaxTree) -> &'t str {
+        match self {
+            WeightUnit::Kilogram(kilogram) => kilogram.text(tree),
+            WeightUnit::Pound(pound) => pound.text(tree),
+        }
+    }
+}
+
+impl_ast_token!(TokenKind::Kilogram);
+impl_ast_token!(TokenKind::Pound);
+
 pub enum WeightLiteral {
     Float(Float),
     Integer(Integer),
@@ -224,7 +931,8 @@ impl WeightLiteral {
     pub fn parse(&self, tree: &SyntaxTree) -> f64 {
         match self {
             WeightLiteral::Float(float) => float.parse(tree),
-            WeightLiteral::Integer(int) => int.parse(tree) as f64,
+            // digits always make a float, however many there are
+            WeightLiteral::Integer(int) => int.text(tree).parse().unwrap(),
         }
     }
 }
@@ -257,6 +965,9 @@ pub enum Quantity {
     Reps(Reps),
     SimpleDuration(SimpleDuration),
     LongDuration(LongDuration),
+    Distance(Distance),
+    RepRange(RepRange),
+    RestPause(RestPause),
 }
 
 impl AstNode for Quantity {
@@ -265,6 +976,9 @@ impl AstNode for Quantity {
             NodeKind::Reps => Some(Self::Reps(Reps(node))),
             NodeKind::SimpleDuration => Some(Self::SimpleDuration(SimpleDuration(node))),
             NodeKind::LongDuration => Some(Self::LongDuration(LongDuration(node))),
+            NodeKind::Distance => Some(Self::Distance(Distance(node))),
+            NodeKind::RepRange => Some(Self::RepRange(RepRange(node))),
+            NodeKind::RestPause => Some(Self::RestPause(RestPause(node))),
             _ => None,
         }
     }
@@ -274,6 +988,9 @@ impl AstNode for Quantity {
             Quantity::Reps(reps) => reps.range(tree),
             Quantity::SimpleDuration(simple) => simple.range(tree),
             Quantity::LongDuration(long) => long.range(tree),
+            Quantity::Distance(distance) => distance.range(tree),
+            Quantity::RepRange(range) => range.range(tree),
+            Quantity::RestPause(rest_pause) => rest_pause.range(tree),
         }
     }
 
@@ -282,6 +999,9 @@ impl AstNode for Quantity {
             Quantity::Reps(reps) => reps.text(tree),
             Quantity::SimpleDuration(simple) => simple.text(tree),
             Quantity::LongDuration(long) => long.text(tree),
+            Quantity::Distance(distance) => distance.text(tree),
+            Quantity::RepRange(range) => range.text(tree),
+            Quantity::RestPause(rest_pause) => rest_pause.text(tree),
         }
     }
 }
@@ -289,9 +1009,36 @@ impl AstNode for Quantity {
 impl_ast_node!(NodeKind::Reps);
 
 impl Reps {
+    /// reps done, ex `4` of `x4/5`
     pub fn amount(&self, tree: &SyntaxTree) -> Option<Integer> {
         find_child_token(&self.0, tree)
     }
+
+    /// reps aimed for, ex `5` of `x4/5`
+    pub fn target(&self, tree: &SyntaxTree) -> Option<Integer> {
+        child_tokens(&self.0, tree).nth(1)
+    }
+
+    /// as many reps as possible, ex `x5+` or `amrap`
+    pub fn is_amrap(&self, tree: &SyntaxTree) -> bool {
+        self.0
+            .child_tokens(tree)
+            .any(|t| matches!(t.kind(tree), TokenKind::Plus | TokenKind::Amrap))
+    }
+
+    /// failed the rep after the ones done, ex `x5F`
+    pub fn is_failure(&self, tree: &SyntaxTree) -> bool {
+        self.0
+            .child_tokens(tree)
+            .any(|t| t.kind(tree) == TokenKind::Fail)
+    }
+
+    /// reps done on each side, ex `x8/side` or `x8 each`
+    pub fn is_per_side(&self, tree: &SyntaxTree) -> bool {
+        self.0
+            .child_tokens(tree)
+            .any(|t| t.kind(tree) == TokenKind::PerSide)
+    }
 }
 
 impl_ast_node!(NodeKind::SimpleDuration);
@@ -375,9 +1122,215 @@ impl LongDuration {
     }
 }
 
+impl_ast_token!(TokenKind::Label);
+
+impl Label {
+    pub fn letter(&self, tree: &SyntaxTree) -> char {
+        self.text(tree).chars().next().unwrap()
+    }
+
+    /// position within the superset, ex `2` of `A2`, `None` when too large to fit
+    pub fn position(&self, tree: &SyntaxTree) -> Option<usize> {
+        self.text(tree)[1..].parse().ok()
+    }
+}
+
+impl_ast_node!(NodeKind::RestPause);
+
+impl RestPause {
+    /// reps of each mini-set, ex `8`, `5` and `3` of `x8+5+3`
+    pub fn reps<'t>(&self, tree: &'t SyntaxTree) -> impl Iterator<Item = Reps> + 't {
+        child_nodes(&self.0, tree)
+    }
+}
+
+impl_ast_node!(NodeKind::RepRange);
+
+impl RepRange {
+    pub fn lower(&self, tree: &SyntaxTree) -> Option<Integer> {
+        find_child_token(&self.0, tree)
+    }
+
+    pub fn upper(&self, tree: &SyntaxTree) -> Option<Integer> {
+        child_tokens(&self.0, tree).nth(1)
+    }
+
+    /// time unit of a duration range, ex `s` of `30-45s`, `None` for reps
+    pub fn unit(&self, tree: &SyntaxTree) -> Option<TimeUnit> {
+        find_child_token(&self.0, tree)
+    }
+}
+
+impl_ast_node!(NodeKind::Distance);
+
+impl Distance {
+    pub fn amount(&self, tree: &SyntaxTree) -> Option<WeightLiteral> {
+        find_child_token(&self.0, tree)
+    }
+
+    pub fn unit(&self, tree: &SyntaxTree) -> Option<DistanceUnit> {
+        find_child_token(&self.0, tree)
+    }
+}
+
+pub enum DistanceUnit {
+    Meter(Meter),
+    Kilometer(Kilometer),
+    Yard(Yard),
+    Foot(Foot),
+    Mile(Mile),
+}
+
+impl AstToken for DistanceUnit {
+    fn cast(token: SyntaxToken, tree: &SyntaxTree) -> Option<Self> {
+        match token.kind(tree) {
+            // only a distance or pace holds a bare `m`, and there it's meters
+            TokenKind::Meter | TokenKind::Minute => Some(Self::Meter(Meter(token))),
+            TokenKind::Kilometer => Some(Self::Kilometer(Kilometer(token))),
+            TokenKind::Yard => Some(Self::Yard(Yard(token))),
+            TokenKind::Foot => Some(Self::Foot(Foot(token))),
+            TokenKind::Mile => Some(Self::Mile(Mile(token))),
+            _ => None,
+        }
+    }
+
+    fn range(&self, tree: &SyntaxTree) -> TextRange {
+        match self {
+            DistanceUnit::Meter(meter) => meter.range(tree),
+            DistanceUnit::Kilometer(kilometer) => kilometer.range(tree),
+            DistanceUnit::Yard(yard) => yard.range(tree),
+            DistanceUnit::Foot(foot) => foot.range(tree),
+            DistanceUnit::Mile(mile) => mile.range(tree),
+        }
+    }
+
+    fn text<'t>(&self, tree: &'t SyntaxTree) -> &'t str {
+        match self {
+            DistanceUnit::Meter(meter) => meter.text(tree),
+            DistanceUnit::Kilometer(kilometer) => kilometer.text(tree),
+            DistanceUnit::Yard(yard) => yard.text(tree),
+            DistanceUnit::Foot(foot) => foot.text(tree),
+            DistanceUnit::Mile(mile) => mile.text(tree),
+        }
+    }
+}
+
+impl_ast_node!(NodeKind::CardioSet);
+
+impl CardioSet {
+    pub fn distance(&self, tree: &SyntaxTree) -> Option<Distance> {
+        find_child_node(&self.0, tree)
+    }
+
+    /// total time, not the time of a pace
+    pub fn duration(&self, tree: &SyntaxTree) -> Option<Duration> {
+        find_child_node(&self.0, tree)
+    }
+
+    pub fn pace(&self, tree: &SyntaxTree) -> Option<Pace> {
+        find_child_node(&self.0, tree)
+    }
+
+    pub fn calories(&self, tree: &SyntaxTree) -> Option<Calories> {
+        find_child_node(&self.0, tree)
+    }
+
+    pub fn heart_rate(&self, tree: &SyntaxTree) -> Option<HeartRate> {
+        find_child_token(&self.0, tree)
+    }
+
+    pub fn effort(&self, tree: &SyntaxTree) -> Option<Effort> {
+        find_child_node(&self.0, tree)
+    }
+
+    pub fn note(&self, tree: &SyntaxTree) -> Option<Note> {
+        find_child_node(&self.0, tree)
+    }
+}
+
+pub enum Duration {
+    Simple(SimpleDuration),
+    Long(LongDuration),
+}
+
+impl AstNode for Duration {
+    fn cast(node: SyntaxNode, tree: &SyntaxTree) -> Option<Self> {
+        match node.kind(tree) {
+            NodeKind::SimpleDuration => Some(Self::Simple(SimpleDuration(node))),
+            NodeKind::LongDuration => Some(Self::Long(LongDuration(node))),
+            _ => None,
+        }
+    }
+
+    fn range(&self, tree: &SyntaxTree) -> TextRange {
+        match self {
+            Duration::Simple(simple) => simple.range(tree),
+            Duration::Long(long) => long.range(tree),
+        }
+    }
+
+    fn text<'t>(&self, tree: &'t SyntaxTree) -> &'t str {
+        match self {
+            Duration::Simple(simple) => simple.text(tree),
+            Duration::Long(long) => long.text(tree),
+        }
+    }
+}
+
+impl_ast_node!(NodeKind::Pace);
+
+impl Pace {
+    /// time per distance, ex `2:05` of `2:05/500m`
+    pub fn duration(&self, tree: &SyntaxTree) -> Option<Duration> {
+        find_child_node(&self.0, tree)
+    }
+
+    /// ex `500` of `2:05/500m`, `None` for `5:30/km`
+    pub fn amount(&self, tree: &SyntaxTree) -> Option<WeightLiteral> {
+        find_child_token(&self.0, tree)
+    }
+
+    pub fn unit(&self, tree: &SyntaxTree) -> Option<DistanceUnit> {
+        find_child_token(&self.0, tree)
+    }
+}
+
+impl_ast_node!(NodeKind::Calories);
+
+impl Calories {
+    pub fn amount(&self, tree: &SyntaxTree) -> Option<Integer> {
+        find_child_token(&self.0, tree)
+    }
+}
+
+impl_ast_token!(TokenKind::HeartRate);
+
+impl HeartRate {
+    /// beats per minute, ex `142` of `hr142`, `None` when too large to fit
+    pub fn bpm(&self, tree: &SyntaxTree) -> Option<usize> {
+        self.text(tree)[2..].parse().ok()
+    }
+}
+
+impl_ast_token!(TokenKind::Meter);
+impl_ast_token!(TokenKind::Kilometer);
+impl_ast_token!(TokenKind::Yard);
+impl_ast_token!(TokenKind::Foot);
+impl_ast_token!(TokenKind::Mile);
+
 impl_ast_token!(TokenKind::Bodyweight);
 impl_ast_token!(TokenKind::Ident);
 
+impl_ast_token!(TokenKind::Text);
+
+impl Text {
+    /// text without the surrounding quotes
+    pub fn value<'t>(&self, tree: &'t SyntaxTree) -> &'t str {
+        let text = self.text(tree);
+        &text[1..text.len() - 1]
+    }
+}
+
 impl_ast_token!(TokenKind::Float);
 
 impl Float {
@@ -389,7 +1342,8 @@ impl Float {
 impl_ast_token!(TokenKind::Integer);
 
 impl Integer {
-    pub fn parse(&self, tree: &SyntaxTree) -> usize {
-        self.text(tree).parse().unwrap()
+    /// `None` when it's too large for a `usize`
+    pub fn parse(&self, tree: &SyntaxTree) -> Option<usize> {
+        self.text(tree).parse().ok()
     }
 }
@@ -89,7 +89,7 @@ impl CompletionTrie {
     }
 }
 
-fn normalize_exercise(exercise: &str) -> String {
+pub(crate) fn normalize_exercise(exercise: &str) -> String {
     let mut output = String::new();
 
     // remove ws and lowercase
@@ -1,118 +1,2888 @@
-use crate::ast::{self, AstToken, SyntaxTree};
+use std::{
+    collections::{BTreeSet, HashMap},
+    ops::Range,
+};
+
+use eventree::TextRange;
+
+use crate::{
+    ast::{self, AstNode, AstToken, SyntaxTree},
+    autocomplete::normalize_exercise,
+};
 
 #[derive(Debug)]
 pub struct Workout {
-    set_groups: Vec<SetGroup>,
+    date: Option<Date>,
+    title: Option<String>,
+    location: Option<String>,
+    /// bodyweight at the time of the session
+    bodyweight: Option<Load>,
+    unit: WeightUnit,
+    /// set groups and supersets before any session heading
+    blocks: Vec<Block>,
+    sessions: Vec<Session>,
 }
 
+/// Set groups under a `## 2026-10-17 Upper A` heading
 #[derive(Debug)]
+pub struct Session {
+    date: Option<Date>,
+    title: Option<String>,
+    tags: BTreeSet<String>,
+    blocks: Vec<Block>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Block {
+    SetGroup(SetGroup),
+    Superset(Superset),
+    /// only kept when lowering with [`RepeatMode::Preserve`]
+    Repeat(Repeat),
+    /// left in place for [`crate::resolve`] to replace with the included blocks
+    Include(Include),
+}
+
+/// `include "warmup.wl"` of another document
+#[derive(Debug, Clone)]
+pub struct Include {
+    path: String,
+    range: TextRange,
+    /// times the include is done, the product of the repeat counts around it
+    repeat_factor: usize,
+}
+
+/// Blocks done a number of times, ex `repeat 3 { ... }` around set groups
+#[derive(Debug, Clone)]
+pub struct Repeat {
+    count: usize,
+    blocks: Vec<Block>,
+}
+
+/// Set groups performed back to back, resting only after each round
+#[derive(Debug, Clone)]
+pub struct Superset {
+    letter: Option<char>,
+    set_groups: Vec<SetGroup>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SupersetKind {
+    Superset,
+    /// three or more set groups
+    Circuit,
+}
+
+/// Calendar date in `YYYY-MM-DD` form
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: u16,
+    month: u8,
+    day: u8,
+}
+
+#[derive(Debug, Clone)]
 pub struct SetGroup {
+    /// label within a superset, ex `A1`
+    label: Option<String>,
+    /// base movement, ex `Squat` of `# Squat (paused, belt)`
     exercise: Option<String>,
+    /// equipment and variations in written order, ex `paused` and `belt`
+    variations: Vec<String>,
+    /// lowercased, ex `heavy` and `comp` of `# Squat [Heavy, comp]`
+    tags: BTreeSet<String>,
+    note: Option<String>,
+    /// training max percentages were resolved against
+    training_max: Option<Load>,
     sets: Vec<Set>,
+    /// only kept when lowering with [`RepeatMode::Preserve`]
+    repeats: Vec<SetRepeat>,
 }
 
-#[derive(Debug)]
+/// Sets of a set group done a number of times, ex `repeat 3 { ... }` around sets
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetRepeat {
+    count: usize,
+    /// indices into the set group's sets
+    sets: Range<usize>,
+}
+
+#[derive(Debug, Clone)]
 pub struct Set {
+    kind: SetKind,
+    /// number of times the set was performed, ex the `3` in `3x5 225`
+    count: usize,
     weight: Option<Weight>,
     quantity: Option<Quantity>,
+    effort: Option<Effort>,
+    tempo: Option<Tempo>,
+    note: Option<String>,
+    /// reps aimed for, ex `5` of `x4/5` or `x5+`
+    target_reps: Option<usize>,
+    amrap: bool,
+    /// failed a rep, ex `x5F` or `x4/5`
+    failure: bool,
+    /// reps done on each side, ex `x8/side`
+    per_side: bool,
+    /// how this mini-set follows the previous one, `None` for the first set of a chain
+    link: Option<SetLink>,
+    /// mini-sets done right after this one, ex `5` and `3` of `x8+5+3`
+    chain: Vec<Set>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SetKind {
+    #[default]
+    Working,
+    /// marked with `w` or `(w)`, left out of working set stats
+    Warmup,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetLink {
+    /// same weight after a short rest, ex `x8+5`
+    RestPause,
+    /// lighter weight right away, ex `225x8 > 185x6`
+    Drop,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Weight {
     Error,
-    Straight(f64),
-    Bodyweight(Option<f64>),
+    Straight(Load),
+    /// load added to bodyweight, negative when assisted, ex `bw - 30`
+    Bodyweight(Option<Load>),
+    /// percentage of a training max that couldn't be resolved
+    Percentage(f64),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WeightUnit {
+    #[default]
+    Pound,
+    Kilogram,
+}
+
+/// A weight amount along with the unit it was recorded in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Load {
+    amount: f64,
+    unit: WeightUnit,
+}
+
+/// How hard a set was, rpe in half steps from 1 to 10 or up to 10 reps left in reserve
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Effort {
+    Rpe(f64),
+    Rir(usize),
+}
+
+/// Seconds spent in each phase of a rep, ex `3-1-1-0`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tempo {
+    eccentric: TempoPhase,
+    bottom: TempoPhase,
+    concentric: TempoPhase,
+    top: TempoPhase,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TempoPhase {
+    Seconds(usize),
+    /// as fast as possible, counted as no time under tension
+    Explosive,
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum Quantity {
     Error,
     Duration(usize),
     Reps(usize),
+    /// Distance in meters.
+    Distance(f64),
+    /// Planned range of reps or durations, ex `x8-12` or `30-45s`.
+    Range {
+        min: usize,
+        max: usize,
+        kind: RangeKind,
+    },
+    /// As many reps as possible without a count, ex `amrap`.
+    Amrap,
+    Cardio(Cardio),
 }
 
-impl Workout {
-    pub fn lower(ast: ast::Workout, tree: &SyntaxTree) -> Self {
-        let set_groups = ast
-            .set_groups(tree)
-            .map(|sg| SetGroup::lower(sg, tree))
-            .collect();
-        Self { set_groups }
+/// What the bounds of a [`Quantity::Range`] count
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeKind {
+    Reps,
+    /// seconds
+    Duration,
+}
+
+/// Conditioning work, ex `2km 7:45 2:05/500m 120cal hr142`
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Cardio {
+    /// meters
+    distance: Option<f64>,
+    /// seconds
+    duration: Option<usize>,
+    pace: Option<Pace>,
+    calories: Option<usize>,
+    /// average beats per minute
+    heart_rate: Option<usize>,
+}
+
+/// Time taken to cover a distance, ex `2:05/500m`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pace {
+    /// seconds
+    duration: usize,
+    /// meters
+    distance: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LowerError {
+    range: TextRange,
+    kind: LowerErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LowerErrorKind {
+    InvalidRpe,
+    InvalidRir,
+    InvalidTrainingMax,
+    MissingTrainingMax,
+    InvalidDate,
+    InvalidBodyweight,
+    InvalidUnit,
+    UndefinedName,
+    DivisionByZero,
+    InvalidRepeatCount,
+    InvalidLabel,
+    InvalidRange,
+    InvalidHeartRate,
+    InvalidSetCount,
+    InvalidReps,
+    InvalidDuration,
+    InvalidCalories,
+}
+
+impl LowerError {
+    pub fn new(range: TextRange, kind: LowerErrorKind) -> Self {
+        Self { range, kind }
+    }
+
+    pub fn range(&self) -> TextRange {
+        self.range
+    }
+
+    pub fn kind(&self) -> &LowerErrorKind {
+        &self.kind
     }
 }
 
-impl SetGroup {
-    fn lower(ast: ast::SetGroup, tree: &SyntaxTree) -> Self {
-        let exercise = ast
-            .exercise(tree)
-            .and_then(|e| e.ident(tree).map(|i| i.text(tree).to_string()));
+/// Caller supplied context for lowering a workout
+#[derive(Debug, Clone, Default)]
+pub struct LowerOptions {
+    unit: WeightUnit,
+    training_maxes: HashMap<String, Load>,
+    repeat_mode: RepeatMode,
+}
+
+/// How `repeat N { ... }` blocks are lowered
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RepeatMode {
+    /// Body copied once per repetition, as if it were written out
+    #[default]
+    Expand,
+    /// Body lowered once and kept along with its count
+    Preserve,
+}
+
+impl LowerOptions {
+    /// Unit for weights that don't specify one when the document doesn't
+    /// declare its own
+    pub fn with_unit(mut self, unit: WeightUnit) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    /// Training max for resolving percentages of `exercise` when the document
+    /// doesn't declare one
+    pub fn with_training_max(mut self, exercise: &str, load: Load) -> Self {
+        self.training_maxes
+            .insert(normalize_exercise(exercise), load);
+        self
+    }
 
-        let sets = ast.sets(tree).map(|s| Set::lower(s, tree)).collect();
+    /// Whether repeat blocks are expanded or kept as written
+    pub fn with_repeat_mode(mut self, mode: RepeatMode) -> Self {
+        self.repeat_mode = mode;
+        self
+    }
 
-        Self { exercise, sets }
+    fn training_max(&self, exercise: &str) -> Option<Load> {
+        self.training_maxes
+            .get(&normalize_exercise(exercise))
+            .copied()
     }
 }
 
-impl Set {
-    fn lower(ast: ast::Set, tree: &SyntaxTree) -> Self {
-        let weight = ast.weight(tree).map(|w| Weight::lower(w, tree));
-        let quantity = ast.quantity(tree).map(|q| Quantity::lower(q, tree));
+struct LowerCtx<'t> {
+    tree: &'t SyntaxTree,
+    options: &'t LowerOptions,
+    /// unit for weights that don't specify one
+    unit: WeightUnit,
+    /// training max of the set group being lowered
+    training_max: Option<Load>,
+    /// values of `let` bindings, `None` when the value couldn't be evaluated
+    bindings: Vec<Binding>,
+    /// times the items being lowered are done, the product of the enclosing repeat counts
+    repeat_factor: usize,
+    errors: Vec<LowerError>,
+}
+
+struct Binding {
+    name: String,
+    range: TextRange,
+    value: Option<f64>,
+    /// unit the value is in, converted where it's used, ex `kg` of `let tm = 140kg`
+    unit: Option<WeightUnit>,
+}
+
+impl LowerCtx<'_> {
+    /// Start lowering the body of a repeat done `count` times, returning the factor
+    /// to restore once the body is lowered
+    fn enter_repeat(&mut self, count: usize) -> usize {
+        let factor = self.repeat_factor;
+        self.repeat_factor *= count.max(1);
+        factor
+    }
+
+    fn error(&mut self, range: TextRange, kind: LowerErrorKind) {
+        self.errors.push(LowerError::new(range, kind))
+    }
+
+    /// Value of an integer, reporting `kind` when it's too large to represent
+    fn integer(&mut self, ast: ast::Integer, kind: LowerErrorKind) -> Option<usize> {
+        let value = ast.parse(self.tree);
+        if value.is_none() {
+            self.error(ast.range(self.tree), kind);
+        }
+        value
+    }
+
+    fn bind(&mut self, ast: ast::Let) {
+        let tree = self.tree;
+        let Some(name) = ast.name(tree) else {
+            return;
+        };
+        let unit = ast.unit(tree).map(lower_weight_unit);
+        let value = ast
+            .value(tree)
+            .and_then(|v| eval(v, unit.unwrap_or(self.unit), self));
+
+        self.bindings.push(Binding {
+            name: name.text(tree).to_string(),
+            range: ast.range(tree),
+            value,
+            unit,
+        });
+    }
 
-        Self { weight, quantity }
+    /// start of bindings only visible until [`Self::end_scope`], ex those of a set group
+    fn scope(&self) -> usize {
+        self.bindings.len()
+    }
+
+    fn end_scope(&mut self, scope: usize) {
+        self.bindings.truncate(scope);
+    }
+
+    /// closest binding of `name` declared before `range` and still in scope
+    fn lookup(&self, name: &str, range: TextRange) -> Option<&Binding> {
+        self.bindings
+            .iter()
+            .filter(|b| b.name == name && b.range.end() <= range.start())
+            .max_by_key(|b| b.range.end())
     }
 }
 
-impl Weight {
-    fn lower(ast: ast::Weight, tree: &SyntaxTree) -> Self {
-        match (ast.weight(tree), ast.bodyweight(tree)) {
-            (Some(weight), Some(_bw)) => Self::Bodyweight(Some(weight.parse(tree))),
-            (Some(weight), None) => Self::Straight(weight.parse(tree)),
-            (None, Some(_bw)) => Self::Bodyweight(None),
-            _ => Self::Error,
+/// Evaluate an expression with `tm` defaulting to the set group's training
+/// max in `unit`
+fn eval(ast: ast::Expr, unit: WeightUnit, ctx: &mut LowerCtx) -> Option<f64> {
+    let tree = ctx.tree;
+    match ast {
+        ast::Expr::Literal(literal) => literal.value(tree).map(|v| v.parse(tree)),
+        ast::Expr::Paren(paren) => eval(paren.expr(tree)?, unit, ctx),
+        ast::Expr::NameRef(name) => {
+            let range = name.range(tree);
+            let text = name.text(tree);
+            if let Some(binding) = ctx.lookup(text, range) {
+                return binding.value.map(|value| match binding.unit {
+                    Some(from) => Load::new(value, from).convert(unit).amount(),
+                    None => value,
+                });
+            }
+
+            match (text, ctx.training_max) {
+                ("tm", Some(tm)) => Some(tm.convert(unit).amount()),
+                ("tm", None) => {
+                    ctx.error(range, LowerErrorKind::MissingTrainingMax);
+                    None
+                }
+                _ => {
+                    ctx.error(range, LowerErrorKind::UndefinedName);
+                    None
+                }
+            }
+        }
+        ast::Expr::Binary(binary) => {
+            let lhs = eval(binary.lhs(tree)?, unit, ctx);
+            let rhs = eval(binary.rhs(tree)?, unit, ctx);
+            let (lhs, rhs) = (lhs?, rhs?);
+
+            match binary.op(tree)? {
+                ast::BinaryOp::Plus(_) => Some(lhs + rhs),
+                ast::BinaryOp::Minus(_) => Some(lhs - rhs),
+                ast::BinaryOp::Star(_) => Some(lhs * rhs),
+                ast::BinaryOp::Slash(_) if rhs == 0.0 => {
+                    ctx.error(binary.range(tree), LowerErrorKind::DivisionByZero);
+                    None
+                }
+                ast::BinaryOp::Slash(_) => Some(lhs / rhs),
+            }
         }
     }
 }
 
-impl Quantity {
-    fn lower(ast: ast::Quantity, tree: &SyntaxTree) -> Self {
-        match ast {
-            ast::Quantity::Reps(reps) => Self::lower_reps(reps, tree),
-            ast::Quantity::SimpleDuration(simple) => Self::lower_simple_duration(simple, tree),
-            ast::Quantity::LongDuration(long) => Self::lower_long_duration(long, tree),
+const POUNDS_PER_KILOGRAM: f64 = 2.204_622_621_848_776;
+/// Most times a repeat can be done, including the counts of the repeats it's nested in,
+/// larger counts are reported rather than expanded
+pub const MAX_REPEAT_COUNT: usize = 1000;
+
+impl WeightUnit {
+    /// Factor to multiply an amount in this unit by to get kilograms
+    fn kilograms_per_unit(self) -> f64 {
+        match self {
+            WeightUnit::Pound => 1.0 / POUNDS_PER_KILOGRAM,
+            WeightUnit::Kilogram => 1.0,
+        }
+    }
+}
+
+impl Load {
+    pub fn new(amount: f64, unit: WeightUnit) -> Self {
+        Self { amount, unit }
+    }
+
+    pub fn amount(&self) -> f64 {
+        self.amount
+    }
+
+    pub fn unit(&self) -> WeightUnit {
+        self.unit
+    }
+
+    pub fn convert(self, unit: WeightUnit) -> Self {
+        if self.unit == unit {
+            return self;
         }
+
+        let kilograms = self.amount * self.unit.kilograms_per_unit();
+        Self::new(kilograms / unit.kilograms_per_unit(), unit)
+    }
+
+    pub fn kilograms(&self) -> f64 {
+        self.convert(WeightUnit::Kilogram).amount
+    }
+
+    pub fn pounds(&self) -> f64 {
+        self.convert(WeightUnit::Pound).amount
     }
+}
+
+impl Date {
+    pub fn new(year: u16, month: u8, day: u8) -> Option<Self> {
+        let leap =
+            year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400));
+        let days = match month {
+            2 if leap => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            1..=12 => 31,
+            _ => return None,
+        };
 
-    fn lower_reps(reps: ast::Reps, tree: &SyntaxTree) -> Self {
-        if let Some(amount) = reps.amount(tree).map(|i| i.parse(tree)) {
-            Self::Reps(amount)
+        if (1..=days).contains(&day) {
+            Some(Self { year, month, day })
         } else {
-            Self::Error
+            None
         }
     }
 
-    fn lower_simple_duration(simple: ast::SimpleDuration, tree: &SyntaxTree) -> Self {
-        let Some(duration) = simple.duration(tree).map(|i| i.parse(tree)) else {
-            return Self::Error;
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut parts = text.trim().splitn(3, '-');
+        let year = parts.next()?.parse().ok()?;
+        let month = parts.next()?.parse().ok()?;
+        let day = parts.next()?.parse().ok()?;
+
+        Self::new(year, month, day)
+    }
+
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+}
+
+impl Workout {
+    /// Lower with unitless weights defaulting to pounds
+    pub fn lower(ast: ast::Workout, tree: &SyntaxTree) -> (Self, Vec<LowerError>) {
+        Self::lower_with(ast, tree, &LowerOptions::default())
+    }
+
+    pub fn lower_with(
+        ast: ast::Workout,
+        tree: &SyntaxTree,
+        options: &LowerOptions,
+    ) -> (Self, Vec<LowerError>) {
+        Self::lower_repeated(ast, tree, options, 1)
+    }
+
+    /// Lower a document whose blocks are done `repeat_factor` times, ex one included
+    /// within repeats, so its own repeats are capped by [`MAX_REPEAT_COUNT`] along with them
+    pub(crate) fn lower_repeated(
+        ast: ast::Workout,
+        tree: &SyntaxTree,
+        options: &LowerOptions,
+        repeat_factor: usize,
+    ) -> (Self, Vec<LowerError>) {
+        let mut ctx = LowerCtx {
+            tree,
+            options,
+            unit: options.unit,
+            training_max: None,
+            bindings: Vec::new(),
+            repeat_factor,
+            errors: Vec::new(),
+        };
+
+        // needs to be known before any weight is lowered
+        if let Some(unit) = ast.unit(tree) {
+            match unit.unit(tree) {
+                Some(ast::WeightUnit::Kilogram(_)) => ctx.unit = WeightUnit::Kilogram,
+                Some(ast::WeightUnit::Pound(_)) => ctx.unit = WeightUnit::Pound,
+                None => ctx.error(unit.range(tree), LowerErrorKind::InvalidUnit),
+            }
+        }
+
+        let date = ast.date(tree).and_then(|date| lower_date(date, &mut ctx));
+        let title = ast.title(tree).and_then(|t| lower_header_text(t, tree));
+        let location = ast.location(tree).and_then(|l| lower_header_text(l, tree));
+        let bodyweight = ast
+            .bodyweight(tree)
+            .and_then(|bw| lower_header_weight(bw, &mut ctx));
+
+        for binding in ast.lets(tree) {
+            ctx.bind(binding);
+        }
+
+        let blocks = Block::lower_all(ast.blocks(tree), &mut ctx);
+        let sessions = ast
+            .sessions(tree)
+            .map(|session| Session::lower(session, &mut ctx))
+            .collect();
+
+        let workout = Self {
+            date,
+            title,
+            location,
+            bodyweight,
+            unit: ctx.unit,
+            blocks,
+            sessions,
+        };
+        (workout, ctx.errors)
+    }
+
+    pub fn date(&self) -> Option<Date> {
+        self.date
+    }
+
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    pub fn location(&self) -> Option<&str> {
+        self.location.as_deref()
+    }
+
+    pub fn bodyweight(&self) -> Option<Load> {
+        self.bodyweight
+    }
+
+    /// Unit used for weights that didn't specify one
+    pub fn unit(&self) -> WeightUnit {
+        self.unit
+    }
+
+    /// Set groups and supersets before any session heading
+    pub fn blocks(&self) -> &[Block] {
+        &self.blocks
+    }
+
+    pub fn sessions(&self) -> &[Session] {
+        &self.sessions
+    }
+
+    pub(crate) fn blocks_mut(&mut self) -> &mut Vec<Block> {
+        &mut self.blocks
+    }
+
+    pub(crate) fn sessions_mut(&mut self) -> &mut [Session] {
+        &mut self.sessions
+    }
+
+    /// Blocks before and within sessions in order, dropping the session headings
+    pub(crate) fn into_blocks(self) -> Vec<Block> {
+        let mut blocks = self.blocks;
+        blocks.extend(self.sessions.into_iter().flat_map(|s| s.blocks));
+        blocks
+    }
+
+    /// Every set group in order, including those within supersets and sessions
+    pub fn set_groups(&self) -> impl Iterator<Item = &SetGroup> {
+        self.blocks
+            .iter()
+            .chain(self.sessions.iter().flat_map(|s| &s.blocks))
+            .flat_map(Block::set_groups)
+    }
+
+    /// Set groups tagged with `tag`, or within a session tagged with it
+    pub fn tagged<'a>(&'a self, tag: &'a str) -> impl Iterator<Item = &'a SetGroup> + 'a {
+        let outside_sessions = self
+            .blocks
+            .iter()
+            .flat_map(Block::set_groups)
+            .filter(move |sg| sg.has_tag(tag));
+        let in_sessions = self.sessions.iter().flat_map(move |session| {
+            let session_tagged = session.has_tag(tag);
+            session
+                .set_groups()
+                .filter(move |sg| session_tagged || sg.has_tag(tag))
+        });
+
+        outside_sessions.chain(in_sessions)
+    }
+}
+
+impl Session {
+    fn lower(ast: ast::Session, ctx: &mut LowerCtx) -> Self {
+        let tree = ctx.tree;
+        let date = ast.date(tree).and_then(|date| lower_date(date, ctx));
+        let title = ast.title(tree).and_then(|t| {
+            let text = t.text(tree).trim();
+            (!text.is_empty()).then(|| text.to_string())
+        });
+        let scope = ctx.scope();
+        for binding in ast.lets(tree) {
+            ctx.bind(binding);
+        }
+        let tags = lower_tags(ast.tags(tree), tree);
+        let blocks = Block::lower_all(ast.blocks(tree), ctx);
+        ctx.end_scope(scope);
+
+        Self {
+            date,
+            title,
+            tags,
+            blocks,
+        }
+    }
+
+    pub fn date(&self) -> Option<Date> {
+        self.date
+    }
+
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    pub fn tags(&self) -> &BTreeSet<String> {
+        &self.tags
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.contains(&tag.to_lowercase())
+    }
+
+    pub fn blocks(&self) -> &[Block] {
+        &self.blocks
+    }
+
+    pub(crate) fn blocks_mut(&mut self) -> &mut Vec<Block> {
+        &mut self.blocks
+    }
+
+    /// Every set group in order, including those within supersets
+    pub fn set_groups(&self) -> impl Iterator<Item = &SetGroup> {
+        self.blocks.iter().flat_map(Block::set_groups)
+    }
+}
+
+impl Block {
+    /// Lower blocks in order, repeats are expanded in place unless preserved
+    fn lower_all(asts: impl Iterator<Item = ast::Block>, ctx: &mut LowerCtx) -> Vec<Self> {
+        let mut blocks = Vec::new();
+        for ast in asts {
+            match ast {
+                ast::Block::SetGroup(sg) => blocks.push(Block::SetGroup(SetGroup::lower(sg, ctx))),
+                ast::Block::Superset(ss) => blocks.push(Block::Superset(Superset::lower(ss, ctx))),
+                ast::Block::Include(include) => {
+                    if let Some(path) = include.path(ctx.tree) {
+                        blocks.push(Block::Include(Include {
+                            path: path.value(ctx.tree).to_string(),
+                            range: include.range(ctx.tree),
+                            repeat_factor: ctx.repeat_factor,
+                        }));
+                    }
+                }
+                ast::Block::Repeat(repeat) => {
+                    let count = Repeat::lower_count(&repeat, ctx);
+                    let scope = ctx.scope();
+                    for binding in repeat.lets(ctx.tree) {
+                        ctx.bind(binding);
+                    }
+                    let factor = ctx.enter_repeat(count);
+                    let body = Self::lower_all(repeat.blocks(ctx.tree), ctx);
+                    ctx.repeat_factor = factor;
+                    ctx.end_scope(scope);
+
+                    match ctx.options.repeat_mode {
+                        RepeatMode::Expand => {
+                            for _ in 0..count {
+                                blocks.extend(body.iter().cloned());
+                            }
+                        }
+                        RepeatMode::Preserve => blocks.push(Block::Repeat(Repeat {
+                            count,
+                            blocks: body,
+                        })),
+                    }
+                }
+            }
+        }
+
+        blocks
+    }
+
+    /// The set group, every set group of the superset or of the repeat's body
+    pub fn set_groups(&self) -> Box<dyn Iterator<Item = &SetGroup> + '_> {
+        match self {
+            Block::SetGroup(sg) => Box::new(std::iter::once(sg)),
+            Block::Superset(ss) => Box::new(ss.set_groups().iter()),
+            Block::Repeat(repeat) => Box::new(repeat.set_groups()),
+            Block::Include(_) => Box::new(std::iter::empty()),
+        }
+    }
+}
+
+impl Include {
+    /// Path as written, relative to the including document
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn range(&self) -> TextRange {
+        self.range
+    }
+
+    pub(crate) fn repeat_factor(&self) -> usize {
+        self.repeat_factor
+    }
+}
+
+impl Repeat {
+    /// Count of a repeat, a missing count is taken as doing the body once, as is
+    /// one that takes the enclosing repeats above [`MAX_REPEAT_COUNT`] so it's never
+    /// expanded
+    fn lower_count(ast: &ast::Repeat, ctx: &mut LowerCtx) -> usize {
+        let Some(count) = ast.count(ctx.tree) else {
+            return 1;
         };
 
-        let multiplier = simple
-            .unit(tree)
-            .map(|unit| match unit {
-                ast::TimeUnit::Hour(_) => 3600,
-                ast::TimeUnit::Minute(_) => 60,
-                ast::TimeUnit::Second(_) => 1,
+        let max = MAX_REPEAT_COUNT / ctx.repeat_factor;
+        match count.parse(ctx.tree) {
+            Some(value @ 1..) if value <= max => value,
+            Some(0) => {
+                ctx.error(count.range(ctx.tree), LowerErrorKind::InvalidRepeatCount);
+                0
+            }
+            _ => {
+                ctx.error(count.range(ctx.tree), LowerErrorKind::InvalidRepeatCount);
+                1
+            }
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Blocks done each time through
+    pub fn blocks(&self) -> &[Block] {
+        &self.blocks
+    }
+
+    pub(crate) fn blocks_mut(&mut self) -> &mut Vec<Block> {
+        &mut self.blocks
+    }
+
+    /// Every set group of the body once
+    pub fn set_groups(&self) -> impl Iterator<Item = &SetGroup> {
+        self.blocks.iter().flat_map(Block::set_groups)
+    }
+}
+
+impl Superset {
+    fn lower(ast: ast::Superset, ctx: &mut LowerCtx) -> Self {
+        let letter = ast.letter(ctx.tree);
+        let set_groups = ast
+            .set_groups(ctx.tree)
+            .map(|sg| SetGroup::lower(sg, ctx))
+            .collect();
+
+        Self { letter, set_groups }
+    }
+
+    pub fn letter(&self) -> Option<char> {
+        self.letter
+    }
+
+    pub fn set_groups(&self) -> &[SetGroup] {
+        &self.set_groups
+    }
+
+    pub fn kind(&self) -> SupersetKind {
+        if self.set_groups.len() > 2 {
+            SupersetKind::Circuit
+        } else {
+            SupersetKind::Superset
+        }
+    }
+
+    /// Number of times through every set group, set groups with fewer sets
+    /// drop out of the later rounds
+    pub fn rounds(&self) -> usize {
+        self.set_groups
+            .iter()
+            .map(|sg| {
+                sg.sets()
+                    .iter()
+                    .enumerate()
+                    .map(|(i, s)| s.count() * sg.repetitions(i))
+                    .sum()
             })
-            .unwrap_or(1);
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+impl SetGroup {
+    fn lower(ast: ast::SetGroup, ctx: &mut LowerCtx) -> Self {
+        let tree = ctx.tree;
+        let label = ast.label(tree).map(|l| {
+            if l.position(tree).is_none() {
+                ctx.error(l.range(tree), LowerErrorKind::InvalidLabel);
+            }
+            l.text(tree).to_string()
+        });
+        let exercise = ast
+            .exercise(tree)
+            .and_then(|e| e.base_name(tree).map(str::to_string));
+        let variations = ast
+            .exercise(tree)
+            .and_then(|e| e.modifiers(tree))
+            .into_iter()
+            .flat_map(|m| m.modifiers(tree))
+            .map(|m| m.name(tree).to_string())
+            .filter(|m| !m.is_empty())
+            .collect();
+        let tags = lower_tags(ast.exercise(tree).and_then(|e| e.tags(tree)), tree);
+
+        // a training max declared in the document wins over the callers
+        ctx.training_max = None;
+        let declared = ast
+            .training_max(tree)
+            .and_then(|tm| Self::lower_training_max(tm, ctx));
+        let training_max = declared.or_else(|| {
+            exercise
+                .as_deref()
+                .and_then(|e| ctx.options.training_max(e))
+        });
+        ctx.training_max = training_max;
+
+        // after the training max so bindings can build on `tm`
+        let scope = ctx.scope();
+        for binding in ast.lets(tree) {
+            ctx.bind(binding);
+        }
+
+        let note = ast.note(tree).and_then(|n| lower_note(n, tree));
+        let mut sets = Vec::new();
+        let mut repeats = Vec::new();
+        Self::lower_items(ast.items(tree), ctx, &mut sets, &mut repeats);
+        ctx.end_scope(scope);
+
+        Self {
+            label,
+            exercise,
+            variations,
+            tags,
+            note,
+            training_max,
+            sets,
+            repeats,
+        }
+    }
+
+    fn lower_items(
+        items: impl Iterator<Item = ast::SetItem>,
+        ctx: &mut LowerCtx,
+        sets: &mut Vec<Set>,
+        repeats: &mut Vec<SetRepeat>,
+    ) {
+        for item in items {
+            match item {
+                ast::SetItem::Set(set) => sets.push(Set::lower(set, ctx)),
+                ast::SetItem::Cardio(cardio) => sets.push(Set::lower_cardio(cardio, ctx)),
+                ast::SetItem::Repeat(repeat) => {
+                    let count = Repeat::lower_count(&repeat, ctx);
+                    let scope = ctx.scope();
+                    for binding in repeat.lets(ctx.tree) {
+                        ctx.bind(binding);
+                    }
+
+                    let start = sets.len();
+                    let factor = ctx.enter_repeat(count);
+                    Self::lower_items(repeat.items(ctx.tree), ctx, sets, repeats);
+                    ctx.repeat_factor = factor;
+                    ctx.end_scope(scope);
+
+                    match ctx.options.repeat_mode {
+                        RepeatMode::Expand => {
+                            let body = sets.split_off(start);
+                            for _ in 0..count {
+                                sets.extend(body.iter().cloned());
+                            }
+                        }
+                        RepeatMode::Preserve => repeats.push(SetRepeat {
+                            count,
+                            sets: start..sets.len(),
+                        }),
+                    }
+                }
+            }
+        }
+    }
+
+    fn lower_training_max(ast: ast::TrainingMax, ctx: &mut LowerCtx) -> Option<Load> {
+        match ast.weight(ctx.tree).map(|w| Weight::lower(w, ctx)) {
+            Some(Weight::Straight(load)) => Some(load),
+            _ => {
+                ctx.error(ast.range(ctx.tree), LowerErrorKind::InvalidTrainingMax);
+                None
+            }
+        }
+    }
+
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    pub fn exercise(&self) -> Option<&str> {
+        self.exercise.as_deref()
+    }
+
+    pub fn variations(&self) -> &[String] {
+        &self.variations
+    }
+
+    /// Whether this is `exercise` done in any variation, ex `# Squat (paused)`
+    /// and `# squat` are both variants of `Squat`
+    pub fn is_variant_of(&self, exercise: &str) -> bool {
+        self.exercise
+            .as_deref()
+            .is_some_and(|e| normalize_exercise(e) == normalize_exercise(exercise))
+    }
+
+    pub fn tags(&self) -> &BTreeSet<String> {
+        &self.tags
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.contains(&tag.to_lowercase())
+    }
+
+    pub fn note(&self) -> Option<&str> {
+        self.note.as_deref()
+    }
+
+    pub fn training_max(&self) -> Option<Load> {
+        self.training_max
+    }
+
+    pub fn sets(&self) -> &[Set] {
+        &self.sets
+    }
+
+    /// Repeats around the sets, innermost first
+    pub fn repeats(&self) -> &[SetRepeat] {
+        &self.repeats
+    }
+
+    /// Times the set at `index` is done through the repeats around it
+    pub fn repetitions(&self, index: usize) -> usize {
+        self.repeats
+            .iter()
+            .filter(|r| r.sets.contains(&index))
+            .map(|r| r.count)
+            .product()
+    }
+
+    /// Total volume of all weighted rep sets, not counting warm-ups
+    pub fn volume(&self, unit: WeightUnit) -> f64 {
+        self.sets
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| s.kind() == SetKind::Working)
+            .filter_map(|(i, s)| Some(s.volume(unit)? * self.repetitions(i) as f64))
+            .sum()
+    }
+}
+
+impl SetRepeat {
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Indices of the repeated sets within the set group
+    pub fn sets(&self) -> Range<usize> {
+        self.sets.clone()
+    }
+}
+
+impl Set {
+    fn lower(ast: ast::Set, ctx: &mut LowerCtx) -> Self {
+        let tree = ctx.tree;
+        let count = ast
+            .set_count(tree)
+            .and_then(|c| c.count(tree))
+            .and_then(|c| ctx.integer(c, LowerErrorKind::InvalidSetCount))
+            .unwrap_or(1);
+        let kind = if ast.is_warmup(tree) {
+            SetKind::Warmup
+        } else {
+            SetKind::Working
+        };
+        let weight = ast.weight(tree).map(|w| Weight::lower(w, ctx));
+        let quantity = ast.quantity(tree).map(|q| Quantity::lower(q, ctx));
+        let effort = ast.effort(tree).and_then(|e| Effort::lower(e, ctx));
+        let tempo = ast.tempo(tree).and_then(|t| Tempo::lower(t, tree));
+        let note = ast.note(tree).and_then(|n| lower_note(n, tree));
+
+        let (mut target_reps, mut amrap, mut failure, mut per_side) = (None, false, false, false);
+        if let Some(ast::Quantity::Reps(reps)) = ast.quantity(tree) {
+            amrap = reps.is_amrap(tree);
+            per_side = reps.is_per_side(tree);
+            target_reps = match reps.target(tree) {
+                Some(target) => ctx.integer(target, LowerErrorKind::InvalidReps),
+                // `x5+` is at least the reps written
+                None if amrap => reps.amount(tree).and_then(|a| a.parse(tree)),
+                None => None,
+            };
+
+            let short = match (&quantity, target_reps) {
+                (Some(Quantity::Reps(done)), Some(target)) => *done < target,
+                _ => false,
+            };
+            failure = reps.is_failure(tree) || short;
+        }
+
+        let mut chain = Vec::new();
+        if let Some(ast::Quantity::RestPause(rest_pause)) = ast.quantity(tree) {
+            // the first reps are the set itself
+            chain.extend(rest_pause.reps(tree).skip(1).map(|reps| Self {
+                kind,
+                count,
+                weight,
+                quantity: Some(Quantity::lower_reps(reps, ctx)),
+                effort: None,
+                tempo,
+                note: None,
+                target_reps: None,
+                amrap: false,
+                failure: false,
+                per_side,
+                link: Some(SetLink::RestPause),
+                chain: Vec::new(),
+            }));
+        }
+
+        if let Some(drop) = ast.drop_set(tree).and_then(|d| d.set(tree)) {
+            let mut drop = Self::lower(drop, ctx);
+            drop.link = Some(SetLink::Drop);
+
+            let rest = std::mem::take(&mut drop.chain);
+            chain.push(drop);
+            chain.extend(rest);
+        }
+
+        // the whole chain is repeated, ex `3x8+5+3 225`
+        for set in &mut chain {
+            set.kind = kind;
+            set.count = count;
+        }
+
+        Self {
+            kind,
+            count,
+            weight,
+            quantity,
+            effort,
+            tempo,
+            note,
+            target_reps,
+            amrap,
+            failure,
+            per_side,
+            link: None,
+            chain,
+        }
+    }
+
+    fn lower_cardio(ast: ast::CardioSet, ctx: &mut LowerCtx) -> Self {
+        let tree = ctx.tree;
+
+        Self {
+            kind: SetKind::Working,
+            count: 1,
+            weight: None,
+            quantity: Some(Quantity::Cardio(Cardio::lower(&ast, ctx))),
+            effort: ast.effort(tree).and_then(|e| Effort::lower(e, ctx)),
+            tempo: None,
+            note: ast.note(tree).and_then(|n| lower_note(n, tree)),
+            target_reps: None,
+            amrap: false,
+            failure: false,
+            per_side: false,
+            link: None,
+            chain: Vec::new(),
+        }
+    }
+
+    pub fn kind(&self) -> SetKind {
+        self.kind
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn weight(&self) -> Option<&Weight> {
+        self.weight.as_ref()
+    }
+
+    pub fn quantity(&self) -> Option<&Quantity> {
+        self.quantity.as_ref()
+    }
+
+    pub fn effort(&self) -> Option<Effort> {
+        self.effort
+    }
+
+    pub fn tempo(&self) -> Option<Tempo> {
+        self.tempo
+    }
+
+    pub fn note(&self) -> Option<&str> {
+        self.note.as_deref()
+    }
+
+    /// Reps aimed for, the reps done are the [`Quantity::Reps`]
+    pub fn target_reps(&self) -> Option<usize> {
+        self.target_reps
+    }
+
+    pub fn is_amrap(&self) -> bool {
+        self.amrap
+    }
+
+    /// Whether a rep was missed, either marked or short of the target
+    pub fn is_failure(&self) -> bool {
+        self.failure
+    }
+
+    pub fn is_per_side(&self) -> bool {
+        self.per_side
+    }
+
+    /// Reps across both sides for per side sets, ex `16` for `x8/side`
+    fn total_reps(&self, reps: usize) -> usize {
+        if self.per_side {
+            reps * 2
+        } else {
+            reps
+        }
+    }
+
+    pub fn link(&self) -> Option<SetLink> {
+        self.link
+    }
+
+    pub fn chain(&self) -> &[Set] {
+        &self.chain
+    }
+
+    /// Seconds under tension across every rep of the set and its chain, `None`
+    /// without a tempo or a rep count
+    pub fn time_under_tension(&self) -> Option<usize> {
+        let tut = match (self.tempo, &self.quantity) {
+            (Some(tempo), Some(Quantity::Reps(reps))) => {
+                tempo.rep_duration() * self.total_reps(*reps) * self.count
+            }
+            _ => return None,
+        };
+
+        Some(
+            tut + self
+                .chain
+                .iter()
+                .filter_map(Set::time_under_tension)
+                .sum::<usize>(),
+        )
+    }
+
+    /// Weight times reps across every repetition of the set and its chain,
+    /// `None` unless the set is a straight weight for reps
+    pub fn volume(&self, unit: WeightUnit) -> Option<f64> {
+        let volume = match (self.weight, &self.quantity) {
+            (Some(Weight::Straight(load)), Some(Quantity::Reps(reps))) => {
+                load.convert(unit).amount() * (self.total_reps(*reps) * self.count) as f64
+            }
+            _ => return None,
+        };
+
+        Some(
+            volume
+                + self
+                    .chain
+                    .iter()
+                    .filter_map(|s| s.volume(unit))
+                    .sum::<f64>(),
+        )
+    }
+}
+
+fn lower_weight_unit(unit: ast::WeightUnit) -> WeightUnit {
+    match unit {
+        ast::WeightUnit::Kilogram(_) => WeightUnit::Kilogram,
+        ast::WeightUnit::Pound(_) => WeightUnit::Pound,
+    }
+}
+
+fn lower_date(ast: impl AstNode, ctx: &mut LowerCtx) -> Option<Date> {
+    let date = Date::parse(ast.text(ctx.tree));
+    if date.is_none() {
+        ctx.error(ast.range(ctx.tree), LowerErrorKind::InvalidDate);
+    }
+    date
+}
+
+fn lower_header_text(ast: ast::HeaderValue, tree: &SyntaxTree) -> Option<String> {
+    let text = ast.text(tree).trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+fn lower_header_weight(ast: ast::HeaderValue, ctx: &mut LowerCtx) -> Option<Load> {
+    let tree = ctx.tree;
+    let Some(amount) = ast.number(tree).map(|n| n.parse(tree)) else {
+        ctx.error(ast.range(tree), LowerErrorKind::InvalidBodyweight);
+        return None;
+    };
+
+    let unit = match ast.unit(tree) {
+        Some(ast::WeightUnit::Kilogram(_)) => WeightUnit::Kilogram,
+        Some(ast::WeightUnit::Pound(_)) => WeightUnit::Pound,
+        None => ctx.unit,
+    };
+    Some(Load::new(amount, unit))
+}
+
+fn lower_tags(ast: Option<ast::Tags>, tree: &SyntaxTree) -> BTreeSet<String> {
+    ast.into_iter()
+        .flat_map(|tags| tags.tags(tree))
+        .map(|tag| tag.name(tree).to_lowercase())
+        .filter(|tag| !tag.is_empty())
+        .collect()
+}
+
+fn lower_note(ast: ast::Note, tree: &SyntaxTree) -> Option<String> {
+    ast.content(tree).map(|t| t.value(tree).to_string())
+}
+
+impl Weight {
+    fn lower(ast: ast::Weight, ctx: &mut LowerCtx) -> Self {
+        let tree = ctx.tree;
+        if ast.percent(tree).is_some() {
+            return Self::lower_percentage(ast, ctx);
+        }
+
+        let unit = ast.unit(tree).map_or(ctx.unit, lower_weight_unit);
+        let load = |weight: ast::WeightLiteral| Load::new(weight.parse(tree), unit);
+
+        let sign = match ast.operator(tree) {
+            Some(ast::WeightOperator::Minus(_)) => -1.0,
+            _ => 1.0,
+        };
+
+        if let Some(expr) = ast.expr(tree) {
+            return match eval(expr, unit, ctx) {
+                Some(amount) => Self::Straight(Load::new(amount, unit)),
+                None => Self::Error,
+            };
+        }
+
+        match (ast.weight(tree), ast.bodyweight(tree)) {
+            (Some(weight), Some(_bw)) => {
+                Self::Bodyweight(Some(Load::new(sign * weight.parse(tree), unit)))
+            }
+            (Some(weight), None) => Self::Straight(load(weight)),
+            (None, Some(_bw)) => Self::Bodyweight(None),
+            _ => Self::Error,
+        }
+    }
+
+    fn lower_percentage(ast: ast::Weight, ctx: &mut LowerCtx) -> Self {
+        let Some(percent) = ast.weight(ctx.tree).map(|w| w.parse(ctx.tree)) else {
+            return Self::Error;
+        };
+
+        match ctx.training_max {
+            Some(tm) => Self::Straight(Load::new(tm.amount() * percent / 100.0, tm.unit())),
+            None => {
+                ctx.error(ast.range(ctx.tree), LowerErrorKind::MissingTrainingMax);
+                Self::Percentage(percent)
+            }
+        }
+    }
+
+    pub fn convert(self, unit: WeightUnit) -> Self {
+        match self {
+            Weight::Error => Weight::Error,
+            Weight::Straight(load) => Weight::Straight(load.convert(unit)),
+            Weight::Bodyweight(added) => Weight::Bodyweight(added.map(|l| l.convert(unit))),
+            Weight::Percentage(percent) => Weight::Percentage(percent),
+        }
+    }
+}
+
+impl Tempo {
+    fn lower(ast: ast::Tempo, tree: &SyntaxTree) -> Option<Self> {
+        let [eccentric, bottom, concentric, top] = ast.phases(tree)?.map(|phase| match phase {
+            ast::TempoPhase::Seconds(seconds) => TempoPhase::Seconds(seconds),
+            ast::TempoPhase::Explosive => TempoPhase::Explosive,
+        });
+
+        Some(Self {
+            eccentric,
+            bottom,
+            concentric,
+            top,
+        })
+    }
+
+    pub fn eccentric(&self) -> TempoPhase {
+        self.eccentric
+    }
+
+    pub fn bottom(&self) -> TempoPhase {
+        self.bottom
+    }
+
+    pub fn concentric(&self) -> TempoPhase {
+        self.concentric
+    }
+
+    pub fn top(&self) -> TempoPhase {
+        self.top
+    }
+
+    /// Seconds a single rep takes
+    pub fn rep_duration(&self) -> usize {
+        [self.eccentric, self.bottom, self.concentric, self.top]
+            .into_iter()
+            .map(|phase| match phase {
+                TempoPhase::Seconds(seconds) => seconds,
+                TempoPhase::Explosive => 0,
+            })
+            .sum()
+    }
+}
+
+impl Effort {
+    fn lower(ast: ast::Effort, ctx: &mut LowerCtx) -> Option<Self> {
+        let tree = ctx.tree;
+        let value = ast.value(tree)?;
+        let amount = value.parse(tree);
+
+        match ast.scale(tree)? {
+            ast::EffortScale::At(_) | ast::EffortScale::Rpe(_) => {
+                // rpe only goes in half steps
+                if (1.0..=10.0).contains(&amount) && (amount * 2.0).fract() == 0.0 {
+                    Some(Self::Rpe(amount))
+                } else {
+                    ctx.error(ast.range(tree), LowerErrorKind::InvalidRpe);
+                    None
+                }
+            }
+            ast::EffortScale::Rir(_) => {
+                // capped at 10 like the rpe scale
+                let rir = match value {
+                    ast::WeightLiteral::Integer(rir) => rir.parse(tree).filter(|&rir| rir <= 10),
+                    ast::WeightLiteral::Float(_) => None,
+                };
+                if rir.is_none() {
+                    ctx.error(ast.range(tree), LowerErrorKind::InvalidRir);
+                }
+                rir.map(Self::Rir)
+            }
+        }
+    }
+
+    /// Estimated reps left in reserve, rpe 10 being none left
+    pub fn reps_in_reserve(&self) -> f64 {
+        match self {
+            Effort::Rpe(rpe) => 10.0 - rpe,
+            Effort::Rir(rir) => *rir as f64,
+        }
+    }
+}
+
+impl Quantity {
+    /// Whether this is a prescription rather than a performed quantity.
+    pub fn is_planned(&self) -> bool {
+        matches!(self, Self::Range { .. } | Self::Amrap)
+    }
+
+    fn lower(ast: ast::Quantity, ctx: &mut LowerCtx) -> Self {
+        let tree = ctx.tree;
+        match ast {
+            ast::Quantity::Reps(reps) => Self::lower_reps(reps, ctx),
+            ast::Quantity::SimpleDuration(simple) => Self::lower_simple_duration(simple, ctx),
+            ast::Quantity::LongDuration(long) => Self::lower_long_duration(long, ctx),
+            ast::Quantity::Distance(distance) => Self::lower_distance(distance, tree),
+            ast::Quantity::RepRange(range) => Self::lower_range(range, ctx),
+            // the rest of the mini-sets are lowered into the set chain
+            ast::Quantity::RestPause(rest_pause) => rest_pause
+                .reps(tree)
+                .next()
+                .map_or(Self::Error, |reps| Self::lower_reps(reps, ctx)),
+        }
+    }
+
+    fn lower_reps(reps: ast::Reps, ctx: &mut LowerCtx) -> Self {
+        let tree = ctx.tree;
+        if let Some(amount) = reps.amount(tree) {
+            ctx.integer(amount, LowerErrorKind::InvalidReps)
+                .map_or(Self::Error, Self::Reps)
+        } else if reps.is_amrap(tree) {
+            Self::Amrap
+        } else {
+            Self::Error
+        }
+    }
+
+    fn lower_duration(duration: ast::Duration, ctx: &mut LowerCtx) -> Self {
+        match duration {
+            ast::Duration::Simple(simple) => Self::lower_simple_duration(simple, ctx),
+            ast::Duration::Long(long) => Self::lower_long_duration(long, ctx),
+        }
+    }
+
+    fn lower_simple_duration(simple: ast::SimpleDuration, ctx: &mut LowerCtx) -> Self {
+        let tree = ctx.tree;
+        let Some(duration) = simple.duration(tree).map(|i| i.parse(tree)) else {
+            return Self::Error;
+        };
+
+        match duration.and_then(|d| d.checked_mul(Self::seconds(simple.unit(tree)))) {
+            Some(seconds) => Self::Duration(seconds),
+            None => {
+                ctx.error(simple.range(tree), LowerErrorKind::InvalidDuration);
+                Self::Error
+            }
+        }
+    }
+
+    fn lower_range(range: ast::RepRange, ctx: &mut LowerCtx) -> Self {
+        let tree = ctx.tree;
+        let (Some(lower), Some(upper)) = (range.lower(tree), range.upper(tree)) else {
+            return Self::Error;
+        };
+
+        let seconds = Self::seconds(range.unit(tree));
+        let bound = |bound: ast::Integer| bound.parse(tree)?.checked_mul(seconds);
+        match (bound(lower), bound(upper)) {
+            (Some(min), Some(max)) if min <= max => Self::Range {
+                min,
+                max,
+                kind: if range.unit(tree).is_some() {
+                    RangeKind::Duration
+                } else {
+                    RangeKind::Reps
+                },
+            },
+            _ => {
+                ctx.error(range.range(tree), LowerErrorKind::InvalidRange);
+                Self::Error
+            }
+        }
+    }
+
+    fn seconds(unit: Option<ast::TimeUnit>) -> usize {
+        unit.map(|unit| match unit {
+            ast::TimeUnit::Hour(_) => 3600,
+            ast::TimeUnit::Minute(_) => 60,
+            ast::TimeUnit::Second(_) => 1,
+        })
+        .unwrap_or(1)
+    }
+
+    fn lower_long_duration(long: ast::LongDuration, ctx: &mut LowerCtx) -> Self {
+        let tree = ctx.tree;
+        let part = |part: Option<ast::Integer>, seconds: usize| {
+            part.map_or(Some(0), |p| p.parse(tree)?.checked_mul(seconds))
+        };
+        let duration = [
+            part(long.hour(tree), 3600),
+            part(long.minute(tree), 60),
+            part(long.second(tree), 1),
+        ]
+        .into_iter()
+        .try_fold(0usize, |total, part| total.checked_add(part?));
+
+        match duration {
+            Some(seconds) => Self::Duration(seconds),
+            None => {
+                ctx.error(long.range(tree), LowerErrorKind::InvalidDuration);
+                Self::Error
+            }
+        }
+    }
+
+    fn lower_distance(distance: ast::Distance, tree: &SyntaxTree) -> Self {
+        let Some(amount) = distance.amount(tree).map(|a| a.parse(tree)) else {
+            return Self::Error;
+        };
+
+        Self::Distance(amount * Self::meters(distance.unit(tree)))
+    }
+
+    fn meters(unit: Option<ast::DistanceUnit>) -> f64 {
+        unit.map(|unit| match unit {
+            ast::DistanceUnit::Meter(_) => 1.0,
+            ast::DistanceUnit::Kilometer(_) => 1000.0,
+            ast::DistanceUnit::Yard(_) => 0.9144,
+            ast::DistanceUnit::Foot(_) => 0.3048,
+            ast::DistanceUnit::Mile(_) => 1609.344,
+        })
+        .unwrap_or(1.0)
+    }
+}
+
+impl Cardio {
+    fn lower(ast: &ast::CardioSet, ctx: &mut LowerCtx) -> Self {
+        let tree = ctx.tree;
+        let seconds = |duration, ctx: &mut LowerCtx| match Quantity::lower_duration(duration, ctx) {
+            Quantity::Duration(seconds) => Some(seconds),
+            _ => None,
+        };
+        let distance = ast
+            .distance(tree)
+            .and_then(|d| match Quantity::lower_distance(d, tree) {
+                Quantity::Distance(meters) => Some(meters),
+                _ => None,
+            });
+        let pace = ast.pace(tree).and_then(|pace| {
+            let amount = pace.amount(tree).map_or(1.0, |a| a.parse(tree));
+            Some(Pace {
+                duration: seconds(pace.duration(tree)?, ctx)?,
+                distance: amount * Quantity::meters(pace.unit(tree)),
+            })
+        });
+
+        let heart_rate = ast.heart_rate(tree).and_then(|hr| {
+            let bpm = hr.bpm(tree);
+            if bpm.is_none() {
+                ctx.error(hr.range(tree), LowerErrorKind::InvalidHeartRate);
+            }
+            bpm
+        });
+
+        Self {
+            distance,
+            duration: ast.duration(tree).and_then(|d| seconds(d, ctx)),
+            pace,
+            calories: ast
+                .calories(tree)
+                .and_then(|c| c.amount(tree))
+                .and_then(|a| ctx.integer(a, LowerErrorKind::InvalidCalories)),
+            heart_rate,
+        }
+    }
+
+    /// Meters covered
+    pub fn distance(&self) -> Option<f64> {
+        self.distance
+    }
+
+    /// Seconds taken
+    pub fn duration(&self) -> Option<usize> {
+        self.duration
+    }
+
+    pub fn pace(&self) -> Option<Pace> {
+        self.pace
+    }
+
+    pub fn calories(&self) -> Option<usize> {
+        self.calories
+    }
+
+    /// Average beats per minute
+    pub fn heart_rate(&self) -> Option<usize> {
+        self.heart_rate
+    }
+}
+
+impl Pace {
+    /// Seconds taken to cover the distance
+    pub fn duration(&self) -> usize {
+        self.duration
+    }
+
+    /// Meters the time is given for, ex `500` of `2:05/500m`
+    pub fn distance(&self) -> f64 {
+        self.distance
+    }
+
+    pub fn seconds_per_kilometer(&self) -> f64 {
+        self.duration as f64 * 1000.0 / self.distance
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse;
+
+    use super::*;
+
+    fn lower_with_errors(input: &str, unit: WeightUnit) -> (Workout, Vec<LowerError>) {
+        let (tree, errors) = parse(input);
+        assert_eq!(errors, []);
+
+        let ast = ast::Workout::cast(tree.root(), &tree).unwrap();
+        Workout::lower_with(ast, &tree, &LowerOptions::default().with_unit(unit))
+    }
+
+    fn lower(input: &str, unit: WeightUnit) -> Workout {
+        let (workout, errors) = lower_with_errors(input, unit);
+        assert_eq!(errors, []);
+
+        workout
+    }
+
+    fn range(start: u32, end: u32) -> TextRange {
+        TextRange::new(start.into(), end.into())
+    }
+
+    fn weights(workout: &Workout) -> Vec<Weight> {
+        workout
+            .set_groups()
+            .flat_map(|sg| sg.sets())
+            .filter_map(|s| s.weight().copied())
+            .collect()
+    }
+
+    #[test]
+    fn load_convert() {
+        let load = Load::new(100.0, WeightUnit::Kilogram);
+        assert!((load.pounds() - 220.462).abs() < 0.001);
+        assert_eq!(load.kilograms(), 100.0);
+
+        let load = Load::new(225.0, WeightUnit::Pound).convert(WeightUnit::Kilogram);
+        assert_eq!(load.unit(), WeightUnit::Kilogram);
+        assert!((load.amount() - 102.058).abs() < 0.001);
+    }
+
+    #[test]
+    fn lower_weight_units() {
+        let workout = lower("# Squat\n225lb x5\n100kg x5\n135 x5", WeightUnit::Kilogram);
+
+        assert_eq!(workout.unit(), WeightUnit::Kilogram);
+        assert_eq!(
+            weights(&workout),
+            [
+                Weight::Straight(Load::new(225.0, WeightUnit::Pound)),
+                Weight::Straight(Load::new(100.0, WeightUnit::Kilogram)),
+                Weight::Straight(Load::new(135.0, WeightUnit::Kilogram)),
+            ]
+        );
+    }
+
+    #[test]
+    fn lower_sets_across() {
+        let workout = lower("# Squat\n3x5 225\n5x5 @ 315\n225x5", WeightUnit::Pound);
+        let squat = workout.set_groups().next().unwrap();
+
+        let counts: Vec<_> = squat.sets().iter().map(Set::count).collect();
+        assert_eq!(counts, [3, 5, 1]);
+        assert_eq!(squat.volume(WeightUnit::Pound), 3375.0 + 7875.0 + 1125.0);
+    }
+
+    #[test]
+    fn lower_bodyweight_units() {
+        let workout = lower("# Dips\nbw + 20kg x5\nbw + 45 x5", WeightUnit::Pound);
+
+        assert_eq!(
+            weights(&workout),
+            [
+                Weight::Bodyweight(Some(Load::new(20.0, WeightUnit::Kilogram))),
+                Weight::Bodyweight(Some(Load::new(45.0, WeightUnit::Pound))),
+            ]
+        );
+    }
+
+    #[test]
+    fn lower_assisted_bodyweight() {
+        let workout = lower("# Pull-ups\nbw - 30 x8\nx10 bw-20kg", WeightUnit::Pound);
+
+        assert_eq!(
+            weights(&workout),
+            [
+                Weight::Bodyweight(Some(Load::new(-30.0, WeightUnit::Pound))),
+                Weight::Bodyweight(Some(Load::new(-20.0, WeightUnit::Kilogram))),
+            ]
+        );
+    }
+
+    #[test]
+    fn lower_x_aliases() {
+        let workout = lower(
+            "# Squat\n225×5\n225*5 reps\n225 x 5 reps\nx8-12 reps 135",
+            WeightUnit::Pound,
+        );
+        let sets: Vec<_> = workout.set_groups().flat_map(|sg| sg.sets()).collect();
+
+        for set in &sets[..3] {
+            assert_eq!(
+                set.weight(),
+                Some(&Weight::Straight(Load::new(225.0, WeightUnit::Pound)))
+            );
+            assert_eq!(set.quantity(), Some(&Quantity::Reps(5)));
+        }
+        assert_eq!(
+            sets[3].quantity(),
+            Some(&Quantity::Range {
+                min: 8,
+                max: 12,
+                kind: RangeKind::Reps
+            })
+        );
+    }
+
+    #[test]
+    fn lower_effort() {
+        let workout = lower(
+            "# Squat\n225 x5 @8\n225 x5 rpe 9.5\nx5 rir2\nx12 @8 rpe 7",
+            WeightUnit::Pound,
+        );
+        let efforts: Vec<_> = workout
+            .set_groups()
+            .next()
+            .unwrap()
+            .sets()
+            .iter()
+            .map(Set::effort)
+            .collect();
+
+        assert_eq!(
+            efforts,
+            [
+                Some(Effort::Rpe(8.0)),
+                Some(Effort::Rpe(9.5)),
+                Some(Effort::Rir(2)),
+                Some(Effort::Rpe(7.0)),
+            ]
+        );
+        // `@` before any weight is the weight, ex a light dumbbell
+        assert_eq!(
+            weights(&workout).last(),
+            Some(&Weight::Straight(Load::new(8.0, WeightUnit::Pound)))
+        );
+        assert_eq!(Effort::Rpe(8.5).reps_in_reserve(), 1.5);
+    }
+
+    #[test]
+    fn lower_effort_out_of_range() {
+        let (workout, errors) = lower_with_errors(
+            "# Squat\n225 x5 @11\n225 x5 @8.2\nx5 rir1.5\nx5 rir99999999999999999999\nx5 rir 50",
+            WeightUnit::Pound,
+        );
+
+        assert!(workout
+            .set_groups()
+            .next()
+            .unwrap()
+            .sets()
+            .iter()
+            .all(|s| s.effort().is_none()));
+        assert_eq!(
+            errors,
+            [
+                LowerError::new(range(15, 18), LowerErrorKind::InvalidRpe),
+                LowerError::new(range(26, 30), LowerErrorKind::InvalidRpe),
+                LowerError::new(range(34, 40), LowerErrorKind::InvalidRir),
+                LowerError::new(range(44, 67), LowerErrorKind::InvalidRir),
+                LowerError::new(range(71, 77), LowerErrorKind::InvalidRir),
+            ]
+        );
+    }
+
+    #[test]
+    fn lower_percentage_declared_training_max() {
+        let workout = lower("# Squat\ntm 140kg\n75% x5\n3x3 @ 85%", WeightUnit::Pound);
+        let squat = workout.set_groups().next().unwrap();
+
+        assert_eq!(
+            squat.training_max(),
+            Some(Load::new(140.0, WeightUnit::Kilogram))
+        );
+        assert_eq!(
+            weights(&workout),
+            [
+                Weight::Straight(Load::new(105.0, WeightUnit::Kilogram)),
+                Weight::Straight(Load::new(119.0, WeightUnit::Kilogram)),
+            ]
+        );
+    }
+
+    #[test]
+    fn lower_percentage_caller_training_max() {
+        let input = "# Back Squat\n50% x5\n\n# Bench Press\ntm 200\n50% x5";
+        let (tree, _) = parse(input);
+        let ast = ast::Workout::cast(tree.root(), &tree).unwrap();
+        let options = LowerOptions::default()
+            .with_training_max("back squat", Load::new(300.0, WeightUnit::Pound))
+            .with_training_max("Bench Press", Load::new(250.0, WeightUnit::Pound));
+        let (workout, errors) = Workout::lower_with(ast, &tree, &options);
+
+        assert_eq!(errors, []);
+        assert_eq!(
+            weights(&workout),
+            [
+                Weight::Straight(Load::new(150.0, WeightUnit::Pound)),
+                Weight::Straight(Load::new(100.0, WeightUnit::Pound)),
+            ]
+        );
+    }
+
+    #[test]
+    fn lower_percentage_missing_training_max() {
+        let (workout, errors) = lower_with_errors("# Squat\n75% x5", WeightUnit::Pound);
+
+        assert_eq!(weights(&workout), [Weight::Percentage(75.0)]);
+        assert_eq!(
+            errors,
+            [LowerError::new(
+                range(8, 12),
+                LowerErrorKind::MissingTrainingMax
+            )]
+        );
+    }
+
+    #[test]
+    fn lower_expressions() {
+        let workout = lower(
+            "let base = 315\n# Squat\nx5 @base - 20\n0.5*base x3\n(base + 5) / 2 x1\n225 - 10 x5\n2*100 x5",
+            WeightUnit::Pound,
+        );
+
+        assert_eq!(
+            weights(&workout),
+            [
+                Weight::Straight(Load::new(295.0, WeightUnit::Pound)),
+                Weight::Straight(Load::new(157.5, WeightUnit::Pound)),
+                Weight::Straight(Load::new(160.0, WeightUnit::Pound)),
+                Weight::Straight(Load::new(215.0, WeightUnit::Pound)),
+                Weight::Straight(Load::new(200.0, WeightUnit::Pound)),
+            ]
+        );
+    }
+
+    #[test]
+    fn lower_expressions_training_max() {
+        let workout = lower(
+            "# Squat\ntm 300\nlet top = 0.9*tm\n0.65*tm x5\ntm - 20kg x3\nx1 @top",
+            WeightUnit::Pound,
+        );
+
+        let tm_kg = Load::new(300.0, WeightUnit::Pound).kilograms();
+        assert_eq!(
+            weights(&workout),
+            [
+                Weight::Straight(Load::new(195.0, WeightUnit::Pound)),
+                Weight::Straight(Load::new(tm_kg - 20.0, WeightUnit::Kilogram)),
+                Weight::Straight(Load::new(270.0, WeightUnit::Pound)),
+            ]
+        );
+    }
+
+    #[test]
+    fn lower_expressions_shadowing() {
+        let workout = lower(
+            "let top = 100\n# Squat\nx1 @top\nlet top = 200\nx1 @top",
+            WeightUnit::Pound,
+        );
+
+        assert_eq!(
+            weights(&workout),
+            [
+                Weight::Straight(Load::new(100.0, WeightUnit::Pound)),
+                Weight::Straight(Load::new(200.0, WeightUnit::Pound)),
+            ]
+        );
+    }
+
+    #[test]
+    fn lower_expressions_units() {
+        let workout = lower(
+            "let tm = 140kg\nlet half = 0.5\n# Squat\nx5 @half*tm kg\ntm x1\nlet top = tm + 10kg\nx1 @top kg",
+            WeightUnit::Pound,
+        );
+
+        let weights = weights(&workout);
+        assert_eq!(
+            weights[0],
+            Weight::Straight(Load::new(70.0, WeightUnit::Kilogram))
+        );
+        assert_eq!(
+            weights[2],
+            Weight::Straight(Load::new(150.0, WeightUnit::Kilogram))
+        );
+        let Weight::Straight(pounds) = weights[1] else {
+            panic!("expected a straight weight");
+        };
+        assert_eq!(pounds.unit(), WeightUnit::Pound);
+        assert!((pounds.kilograms() - 140.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn lower_expressions_scope() {
+        let input = "let base = 100\n# Squat\nlet top = 200\nx1 @top\nrepeat 2 {\nlet back = 150\nx5 @back\n}\nx5 @back\n# Bench\nx1 @top\nx1 @base\n\n## Day 1\nlet day = 50\n# Row\nx8 @day\n\n## Day 2\n# Row\nx8 @day\nx8 @base";
+        let (workout, errors) = lower_with_errors(input, WeightUnit::Pound);
+
+        let pounds = |amount| Weight::Straight(Load::new(amount, WeightUnit::Pound));
+        assert_eq!(
+            weights(&workout),
+            [
+                pounds(200.0),
+                pounds(150.0),
+                pounds(150.0),
+                Weight::Error,
+                Weight::Error,
+                pounds(100.0),
+                pounds(50.0),
+                Weight::Error,
+                pounds(100.0),
+            ]
+        );
+        assert_eq!(
+            errors,
+            [
+                LowerError::new(range(86, 90), LowerErrorKind::UndefinedName),
+                LowerError::new(range(103, 106), LowerErrorKind::UndefinedName),
+                LowerError::new(range(173, 176), LowerErrorKind::UndefinedName),
+            ]
+        );
+    }
+
+    #[test]
+    fn lower_expressions_let_between_set_groups() {
+        let (workout, errors) = lower_with_errors(
+            "# Squat\n225 x5\n\nlet heavy = 315\n# Bench\nx5 @heavy\n\n## Day 1\n# Row\nx8 135\n\nlet day = 50\n# Curl\nx12 @day",
+            WeightUnit::Pound,
+        );
+
+        let weights = weights(&workout);
+        assert_eq!(
+            weights[1],
+            Weight::Straight(Load::new(315.0, WeightUnit::Pound))
+        );
+        assert_eq!(
+            weights[3],
+            Weight::Straight(Load::new(50.0, WeightUnit::Pound))
+        );
+        assert_eq!(errors, []);
+    }
+
+    #[test]
+    fn lower_expressions_invalid() {
+        let (workout, errors) = lower_with_errors(
+            "# Squat\nx5 @heavy\n1 / 0 x3\n0.5*tm x1\nlet heavy = 1",
+            WeightUnit::Pound,
+        );
+
+        assert_eq!(
+            weights(&workout),
+            [Weight::Error, Weight::Error, Weight::Error]
+        );
+        assert_eq!(
+            errors,
+            [
+                LowerError::new(range(12, 17), LowerErrorKind::UndefinedName),
+                LowerError::new(range(18, 23), LowerErrorKind::DivisionByZero),
+                LowerError::new(range(31, 33), LowerErrorKind::MissingTrainingMax),
+            ]
+        );
+    }
+
+    #[test]
+    fn lower_notes() {
+        let workout = lower(
+            "# Squat \"low bar\"\n225 x5 \"belt on\"\n245 x5",
+            WeightUnit::Pound,
+        );
+        let squat = workout.set_groups().next().unwrap();
+
+        assert_eq!(squat.note(), Some("low bar"));
+        let notes: Vec<_> = squat.sets().iter().map(Set::note).collect();
+        assert_eq!(notes, [Some("belt on"), None]);
+    }
+
+    #[test]
+    fn lower_header() {
+        let workout = lower(
+            "---\ndate: 2026-10-17\ntitle: Upper A\nbodyweight: 84.5kg\nlocation: Home gym\n---\n# Bench Press\n225 x5",
+            WeightUnit::Pound,
+        );
+
+        assert_eq!(workout.date(), Date::new(2026, 10, 17));
+        assert_eq!(workout.title(), Some("Upper A"));
+        assert_eq!(workout.location(), Some("Home gym"));
+        assert_eq!(
+            workout.bodyweight(),
+            Some(Load::new(84.5, WeightUnit::Kilogram))
+        );
+    }
+
+    #[test]
+    fn lower_header_unit() {
+        let workout = lower(
+            "---\nunit: kg\nbodyweight: 85\n---\n# Bench Press\n100 x5",
+            WeightUnit::Pound,
+        );
+
+        assert_eq!(workout.unit(), WeightUnit::Kilogram);
+        assert_eq!(
+            workout.bodyweight(),
+            Some(Load::new(85.0, WeightUnit::Kilogram))
+        );
+        assert_eq!(
+            weights(&workout),
+            [Weight::Straight(Load::new(100.0, WeightUnit::Kilogram))]
+        );
+    }
+
+    #[test]
+    fn lower_header_invalid() {
+        let (workout, errors) = lower_with_errors(
+            "---\ndate: 2026-13-01\nbodyweight: heavy\nunit: stone\n---",
+            WeightUnit::Pound,
+        );
+
+        assert_eq!(workout.date(), None);
+        assert_eq!(workout.bodyweight(), None);
+        assert_eq!(
+            errors,
+            [
+                LowerError::new(range(45, 50), LowerErrorKind::InvalidUnit),
+                LowerError::new(range(10, 20), LowerErrorKind::InvalidDate),
+                LowerError::new(range(33, 38), LowerErrorKind::InvalidBodyweight),
+            ]
+        );
+    }
+
+    #[test]
+    fn date_days_per_month() {
+        assert!(Date::new(2026, 1, 31).is_some());
+        assert!(Date::new(2026, 4, 31).is_none());
+        assert!(Date::new(2026, 2, 29).is_none());
+        assert!(Date::new(2024, 2, 29).is_some());
+        assert!(Date::new(1900, 2, 29).is_none());
+        assert!(Date::new(2000, 2, 29).is_some());
+        assert!(Date::new(2026, 6, 0).is_none());
+        assert_eq!(Date::parse("2026-02-30"), None);
+    }
+
+    #[test]
+    fn lower_distances() {
+        let workout = lower(
+            "# Carry\n70kg 40meters\n20yds 180lb\n\n# Run\n1.5mi bw\nbw 5km\nbw 100ft",
+            WeightUnit::Pound,
+        );
+
+        let quantities: Vec<_> = workout
+            .set_groups()
+            .flat_map(|sg| sg.sets())
+            .filter_map(|s| s.quantity())
+            .collect();
+
+        assert_eq!(
+            quantities,
+            [
+                &Quantity::Distance(40.0),
+                &Quantity::Distance(20.0 * 0.9144),
+                &Quantity::Distance(1.5 * 1609.344),
+                &Quantity::Distance(5000.0),
+                &Quantity::Distance(100.0 * 0.3048),
+            ]
+        );
+    }
+
+    #[test]
+    fn lower_cardio() {
+        let workout = lower(
+            "# Row\n2km 7:45 2:05/500m 120cal hr142 rpe8 \"steady\"\n# Bike\nhr150 1:00:00\n5:30/km",
+            WeightUnit::Pound,
+        );
+
+        let cardio: Vec<_> = workout
+            .set_groups()
+            .flat_map(|sg| sg.sets())
+            .filter_map(|s| match s.quantity() {
+                Some(Quantity::Cardio(cardio)) => Some(*cardio),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(cardio.len(), 3);
+
+        let row = cardio[0];
+        assert_eq!(row.distance(), Some(2000.0));
+        assert_eq!(row.duration(), Some(7 * 60 + 45));
+        assert_eq!(row.calories(), Some(120));
+        assert_eq!(row.heart_rate(), Some(142));
+        let pace = row.pace().unwrap();
+        assert_eq!(pace.duration(), 125);
+        assert_eq!(pace.distance(), 500.0);
+        assert_eq!(pace.seconds_per_kilometer(), 250.0);
+
+        let set = &workout.set_groups().next().unwrap().sets()[0];
+        assert_eq!(set.effort(), Some(Effort::Rpe(8.0)));
+        assert_eq!(set.note(), Some("steady"));
+
+        assert_eq!(cardio[1].heart_rate(), Some(150));
+        assert_eq!(cardio[1].duration(), Some(3600));
+        assert_eq!(cardio[1].distance(), None);
+        assert_eq!(cardio[2].pace().unwrap().distance(), 1000.0);
+        assert_eq!(cardio[2].pace().unwrap().duration(), 330);
+    }
+
+    #[test]
+    fn lower_invalid_heart_rate() {
+        let (workout, errors) =
+            lower_with_errors("# Row\n2km hr99999999999999999999", WeightUnit::Pound);
+
+        let Some(Quantity::Cardio(row)) = workout.set_groups().next().unwrap().sets()[0].quantity()
+        else {
+            panic!("expected cardio");
+        };
+        assert_eq!(row.heart_rate(), None);
+        assert_eq!(row.distance(), Some(2000.0));
+        assert_eq!(
+            errors,
+            [LowerError::new(
+                range(10, 32),
+                LowerErrorKind::InvalidHeartRate
+            )]
+        );
+    }
+
+    #[test]
+    fn lower_integers_too_large() {
+        let (workout, errors) = lower_with_errors(
+            "# Squat\n99999999999999999999x5 225\n225x99999999999999999999\n\n# Plank\n9999999999999999999h bw\n99999999999999999:00:00 bw",
+            WeightUnit::Pound,
+        );
+
+        let sets: Vec<_> = workout.set_groups().flat_map(|sg| sg.sets()).collect();
+        assert_eq!(sets[0].count(), 1);
+        assert_eq!(sets[0].quantity(), Some(&Quantity::Reps(5)));
+        assert_eq!(sets[1].quantity(), Some(&Quantity::Error));
+        assert_eq!(sets[2].quantity(), Some(&Quantity::Error));
+        assert_eq!(sets[3].quantity(), Some(&Quantity::Error));
+        assert_eq!(
+            errors,
+            [
+                LowerError::new(range(8, 28), LowerErrorKind::InvalidSetCount),
+                LowerError::new(range(39, 59), LowerErrorKind::InvalidReps),
+                LowerError::new(range(69, 89), LowerErrorKind::InvalidDuration),
+                LowerError::new(range(93, 116), LowerErrorKind::InvalidDuration),
+            ]
+        );
+    }
+
+    #[test]
+    fn lower_invalid_calories() {
+        let (workout, errors) =
+            lower_with_errors("# Row\n2km 99999999999999999999999cal", WeightUnit::Pound);
+
+        let Some(Quantity::Cardio(row)) = workout.set_groups().next().unwrap().sets()[0].quantity()
+        else {
+            panic!("expected cardio");
+        };
+        assert_eq!(row.calories(), None);
+        assert_eq!(row.distance(), Some(2000.0));
+        assert_eq!(
+            errors,
+            [LowerError::new(
+                range(10, 33),
+                LowerErrorKind::InvalidCalories
+            )]
+        );
+    }
+
+    #[test]
+    fn lower_bare_m() {
+        let workout = lower(
+            "# Carry\n70kg 40m\n40m 70kg\n\n# Plank\nbw 2m\n\n# Row\n500m 1:45\n30m 5km\n500m 2:05/500m 1:45",
+            WeightUnit::Pound,
+        );
+        let sets: Vec<_> = workout.set_groups().flat_map(|sg| sg.sets()).collect();
+        let cardio = |i: usize| match sets[i].quantity() {
+            Some(Quantity::Cardio(cardio)) => *cardio,
+            _ => panic!("expected cardio"),
+        };
+
+        assert_eq!(sets[0].quantity(), Some(&Quantity::Distance(40.0)));
+        assert_eq!(sets[1].quantity(), Some(&Quantity::Distance(40.0)));
+        assert_eq!(sets[2].quantity(), Some(&Quantity::Duration(120)));
+
+        assert_eq!(cardio(3).distance(), Some(500.0));
+        assert_eq!(cardio(3).duration(), Some(105));
+        assert_eq!(cardio(4).distance(), Some(5000.0));
+        assert_eq!(cardio(4).duration(), Some(1800));
+        assert_eq!(cardio(5).distance(), Some(500.0));
+        assert_eq!(cardio(5).pace().unwrap().distance(), 500.0);
+    }
+
+    #[test]
+    fn lower_rep_ranges() {
+        let workout = lower(
+            "# Bench\n3x8-12 135\n\n# Plank\n30-45s bw",
+            WeightUnit::Pound,
+        );
+        let sets: Vec<_> = workout.set_groups().flat_map(|sg| sg.sets()).collect();
+
+        let reps = sets[0].quantity().unwrap();
+        assert!(reps.is_planned());
+        assert_eq!(
+            reps,
+            &Quantity::Range {
+                min: 8,
+                max: 12,
+                kind: RangeKind::Reps
+            }
+        );
+        assert_eq!(sets[0].count(), 3);
+        assert_eq!(sets[0].volume(WeightUnit::Pound), None);
+
+        assert_eq!(
+            sets[1].quantity(),
+            Some(&Quantity::Range {
+                min: 30,
+                max: 45,
+                kind: RangeKind::Duration
+            })
+        );
+    }
+
+    #[test]
+    fn lower_invalid_range() {
+        let (workout, errors) = lower_with_errors(
+            "# Bench\nx12-8 135\n2-1m bw\nx8-99999999999999999999 135\n1-9999999999999999999m bw",
+            WeightUnit::Pound,
+        );
+        let sets: Vec<_> = workout.set_groups().flat_map(|sg| sg.sets()).collect();
+
+        assert_eq!(
+            errors,
+            [
+                LowerError::new(range(8, 13), LowerErrorKind::InvalidRange),
+                LowerError::new(range(18, 22), LowerErrorKind::InvalidRange),
+                LowerError::new(range(26, 49), LowerErrorKind::InvalidRange),
+                LowerError::new(range(54, 76), LowerErrorKind::InvalidRange),
+            ]
+        );
+        assert_eq!(sets[0].quantity(), Some(&Quantity::Error));
+        assert_eq!(sets[2].quantity(), Some(&Quantity::Error));
+    }
+
+    #[test]
+    fn lower_comma_separated_sets() {
+        let workout = lower("# Squat\n225x5, 245x3, 265x1\n275 x1", WeightUnit::Pound);
+
+        assert_eq!(
+            weights(&workout),
+            [225.0, 245.0, 265.0, 275.0]
+                .map(|amount| Weight::Straight(Load::new(amount, WeightUnit::Pound)))
+        );
+        let reps: Vec<_> = workout
+            .set_groups()
+            .flat_map(|sg| sg.sets())
+            .map(|s| s.quantity().cloned())
+            .collect();
+        assert_eq!(reps, [5, 3, 1, 1].map(|reps| Some(Quantity::Reps(reps))));
+    }
+
+    #[test]
+    fn lower_tempo() {
+        let workout = lower(
+            "# Squat\n3x5 225 3-1-1-0\n\n# Pull-ups\nx8 t:31X0",
+            WeightUnit::Pound,
+        );
+        let sets: Vec<_> = workout.set_groups().flat_map(|sg| sg.sets()).collect();
+
+        let tempo = sets[0].tempo().unwrap();
+        assert_eq!(tempo.eccentric(), TempoPhase::Seconds(3));
+        assert_eq!(tempo.top(), TempoPhase::Seconds(0));
+        assert_eq!(sets[0].time_under_tension(), Some(5 * 5 * 3));
+
+        let tempo = sets[1].tempo().unwrap();
+        assert_eq!(tempo.concentric(), TempoPhase::Explosive);
+        assert_eq!(tempo.rep_duration(), 4);
+        assert_eq!(sets[1].time_under_tension(), Some(32));
+
+        let workout = lower("# Squat\n225x5 3-1-1-0", WeightUnit::Pound);
+        let set = &workout.set_groups().next().unwrap().sets()[0];
+        assert_eq!(
+            set.weight(),
+            Some(&Weight::Straight(Load::new(225.0, WeightUnit::Pound)))
+        );
+        assert_eq!(set.time_under_tension(), Some(5 * 5));
+    }
+
+    #[test]
+    fn lower_rest_pause() {
+        let workout = lower("# Curl\n2x8+5+3 30", WeightUnit::Pound);
+        let set = workout
+            .set_groups()
+            .flat_map(|sg| sg.sets())
+            .next()
+            .unwrap();
+
+        assert_eq!(set.quantity(), Some(&Quantity::Reps(8)));
+        assert_eq!(set.link(), None);
+
+        let chain = set.chain();
+        assert_eq!(chain.len(), 2);
+        assert_eq!(chain[0].link(), Some(SetLink::RestPause));
+        assert_eq!(chain[1].quantity(), Some(&Quantity::Reps(3)));
+        assert_eq!(chain[1].weight(), set.weight());
+        assert_eq!(chain[1].count(), 2);
+
+        assert_eq!(set.volume(WeightUnit::Pound), Some(30.0 * 16.0 * 2.0));
+    }
+
+    #[test]
+    fn lower_drop_set() {
+        let workout = lower("# Curl\n225x8 > 185x6", WeightUnit::Pound);
+        let set = &workout.set_groups().next().unwrap().sets()[0];
+        let links: Vec<_> = [set]
+            .into_iter()
+            .chain(set.chain())
+            .map(|s| (s.weight().copied(), s.quantity().cloned()))
+            .collect();
+        assert_eq!(
+            links,
+            [(225.0, 8), (185.0, 6)].map(|(weight, reps)| (
+                Some(Weight::Straight(Load::new(weight, WeightUnit::Pound))),
+                Some(Quantity::Reps(reps))
+            ))
+        );
+        assert_eq!(
+            set.volume(WeightUnit::Pound),
+            Some(225.0 * 8.0 + 185.0 * 6.0)
+        );
+
+        let workout = lower("# Curl\n225x8 > 185x6+2 > 135x10 rpe10", WeightUnit::Pound);
+        let sets: Vec<_> = workout.set_groups().flat_map(|sg| sg.sets()).collect();
+        assert_eq!(sets.len(), 1);
+
+        let chain = sets[0].chain();
+        assert_eq!(
+            chain.iter().map(|s| s.link()).collect::<Vec<_>>(),
+            [
+                Some(SetLink::Drop),
+                Some(SetLink::RestPause),
+                Some(SetLink::Drop)
+            ]
+        );
+        assert_eq!(
+            chain[1].weight(),
+            Some(&Weight::Straight(Load::new(185.0, WeightUnit::Pound)))
+        );
+        assert_eq!(chain[2].effort(), Some(Effort::Rpe(10.0)));
+
+        assert_eq!(
+            sets[0].volume(WeightUnit::Pound),
+            Some(225.0 * 8.0 + 185.0 * 8.0 + 135.0 * 10.0)
+        );
+    }
+
+    #[test]
+    fn lower_amrap_and_failure() {
+        let workout = lower(
+            "# Squat\n3x5+ 225\n245 amrap\n255x4/5\n255x3F\n185x5",
+            WeightUnit::Pound,
+        );
+        let sets: Vec<_> = workout.set_groups().flat_map(|sg| sg.sets()).collect();
+
+        assert!(sets[0].is_amrap());
+        assert_eq!(sets[0].count(), 3);
+        assert_eq!(sets[0].target_reps(), Some(5));
+        assert!(!sets[0].is_failure());
+
+        assert!(sets[1].is_amrap());
+        assert_eq!(sets[1].quantity(), Some(&Quantity::Amrap));
+        assert!(sets[1].quantity().unwrap().is_planned());
+
+        assert_eq!(sets[2].quantity(), Some(&Quantity::Reps(4)));
+        assert_eq!(sets[2].target_reps(), Some(5));
+        assert!(sets[2].is_failure());
+
+        assert_eq!(sets[3].quantity(), Some(&Quantity::Reps(3)));
+        assert!(sets[3].is_failure());
+
+        assert!(!sets[4].is_amrap());
+        assert_eq!(sets[4].target_reps(), None);
+        assert!(!sets[4].is_failure());
+
+        // markers don't change which number is the weight
+        assert_eq!(
+            weights(&workout)[2..],
+            [255.0, 255.0, 185.0].map(|w| Weight::Straight(Load::new(w, WeightUnit::Pound)))
+        );
+        assert_eq!(sets[4].quantity(), Some(&Quantity::Reps(5)));
+    }
+
+    #[test]
+    fn lower_markers_keep_the_weight() {
+        let workout = lower("# Squat\n225x5\n225x5+\n225x5F\n225x4/5", WeightUnit::Pound);
+        let sets: Vec<_> = workout.set_groups().flat_map(|sg| sg.sets()).collect();
+
+        for set in &sets {
+            assert_eq!(
+                set.weight(),
+                Some(&Weight::Straight(Load::new(225.0, WeightUnit::Pound)))
+            );
+        }
+        let flags: Vec<_> = sets
+            .iter()
+            .map(|s| (s.quantity(), s.is_amrap(), s.is_failure(), s.target_reps()))
+            .collect();
+        assert_eq!(
+            flags,
+            [
+                (Some(&Quantity::Reps(5)), false, false, None),
+                (Some(&Quantity::Reps(5)), true, false, Some(5)),
+                (Some(&Quantity::Reps(5)), false, true, None),
+                (Some(&Quantity::Reps(4)), false, true, Some(5)),
+            ]
+        );
+    }
+
+    #[test]
+    fn lower_target_too_large() {
+        let (workout, errors) =
+            lower_with_errors("# Squat\n255x4/99999999999999999999", WeightUnit::Pound);
+        let set = &workout.set_groups().next().unwrap().sets()[0];
+
+        assert_eq!(set.quantity(), Some(&Quantity::Reps(4)));
+        assert_eq!(set.target_reps(), None);
+        assert!(!set.is_failure());
+        assert_eq!(
+            errors,
+            [LowerError::new(range(14, 34), LowerErrorKind::InvalidReps)]
+        );
+    }
+
+    #[test]
+    fn lower_per_side() {
+        let workout = lower(
+            "# Single Arm Row\n3x8/side 70\n\n# Lunge\n50 x10 each\n\n# Each Arm Curl\n30 x8",
+            WeightUnit::Pound,
+        );
+        let sets: Vec<_> = workout.set_groups().flat_map(|sg| sg.sets()).collect();
+
+        let curl = workout.set_groups().nth(2).unwrap();
+        assert_eq!(curl.exercise(), Some("Each Arm Curl"));
+        assert!(!curl.sets()[0].is_per_side());
+
+        assert!(sets[0].is_per_side());
+        assert_eq!(sets[0].quantity(), Some(&Quantity::Reps(8)));
+        assert_eq!(sets[0].volume(WeightUnit::Pound), Some(70.0 * 16.0 * 3.0));
+
+        assert!(sets[1].is_per_side());
+        assert_eq!(sets[1].volume(WeightUnit::Pound), Some(50.0 * 20.0));
+
+        // the marker only sets the flag, the weight is read the same with or without it
+        let workout = lower(
+            "# Row\n135x8\n135x8/side\n135x8 each\n135x8 reps each\n8 reps each 135",
+            WeightUnit::Pound,
+        );
+        let sets: Vec<_> = workout.set_groups().flat_map(|sg| sg.sets()).collect();
+        for set in &sets {
+            assert_eq!(
+                set.weight(),
+                Some(&Weight::Straight(Load::new(135.0, WeightUnit::Pound)))
+            );
+            assert_eq!(set.quantity(), Some(&Quantity::Reps(8)));
+        }
+        let per_side: Vec<_> = sets.iter().map(|s| s.is_per_side()).collect();
+        assert_eq!(per_side, [false, true, true, true, true]);
+    }
+
+    #[test]
+    fn lower_warmup() {
+        let workout = lower("# Squat\nw 135x5\n(w) 185x3\n2x5 225", WeightUnit::Pound);
+        let squat = workout.set_groups().next().unwrap();
+
+        let kinds: Vec<_> = squat.sets().iter().map(Set::kind).collect();
+        assert_eq!(kinds, [SetKind::Warmup, SetKind::Warmup, SetKind::Working]);
+        assert_eq!(squat.sets()[0].volume(WeightUnit::Pound), Some(675.0));
+        assert_eq!(squat.volume(WeightUnit::Pound), 2250.0);
+    }
+
+    #[test]
+    fn lower_sessions() {
+        let (workout, errors) = lower_with_errors(
+            "# Warmup\nbw x20\n## 2026-10-17 Upper A\n# Bench\n225 x5\n\n## 2026-13-40\nA1 # Squat\n315 x5\nA2 # Plank\nbw 1:00\n## Lower B\n# Deadlift\n405 x3",
+            WeightUnit::Pound,
+        );
+
+        assert_eq!(
+            errors,
+            [LowerError::new(range(57, 67), LowerErrorKind::InvalidDate)]
+        );
+
+        assert_eq!(workout.blocks().len(), 1);
+        let [upper, invalid, lower] = workout.sessions() else {
+            panic!("expected three sessions");
+        };
+
+        assert_eq!(upper.date(), Date::new(2026, 10, 17));
+        assert_eq!(upper.title(), Some("Upper A"));
+        assert_eq!(upper.set_groups().count(), 1);
+
+        assert_eq!(invalid.date(), None);
+        assert_eq!(invalid.title(), None);
+        assert_eq!(invalid.set_groups().count(), 2);
+
+        assert_eq!(lower.date(), None);
+        assert_eq!(lower.title(), Some("Lower B"));
+
+        let exercises: Vec<_> = workout
+            .set_groups()
+            .filter_map(SetGroup::exercise)
+            .collect();
+        assert_eq!(exercises, ["Warmup", "Bench", "Squat", "Plank", "Deadlift"]);
+    }
+
+    #[test]
+    fn lower_supersets() {
+        let workout = lower(
+            "A1 # Bench Press\n3x5 225\nA2 # Pull-ups\n3x8 bw\n\nB1 # Curl\n30 x12\nB2 # Pushdown\n50 x12\nB3 # Face Pull\n40 x15\n40 x15\n\n# Plank\nbw 1:00",
+            WeightUnit::Pound,
+        );
+
+        let [Block::Superset(a), Block::Superset(b), Block::SetGroup(plank)] = workout.blocks()
+        else {
+            panic!("expected two supersets then a set group");
+        };
+
+        assert_eq!(a.letter(), Some('A'));
+        assert_eq!(a.kind(), SupersetKind::Superset);
+        assert_eq!(a.rounds(), 3);
+        assert_eq!(a.set_groups()[1].label(), Some("A2"));
+
+        assert_eq!(b.letter(), Some('B'));
+        assert_eq!(b.kind(), SupersetKind::Circuit);
+        assert_eq!(b.rounds(), 2);
+
+        assert_eq!(plank.label(), None);
+        assert_eq!(workout.set_groups().count(), 6);
+    }
+
+    #[test]
+    fn lower_invalid_label() {
+        let (workout, errors) = lower_with_errors(
+            "A99999999999999999999999 # Row\n135x8\nA2 # B2 Bench\n185x5",
+            WeightUnit::Pound,
+        );
+
+        assert_eq!(
+            errors,
+            [LowerError::new(range(0, 24), LowerErrorKind::InvalidLabel)]
+        );
+        let names: Vec<_> = workout
+            .set_groups()
+            .filter_map(|sg| sg.exercise())
+            .collect();
+        assert_eq!(names, ["Row", "B2 Bench"]);
+    }
+
+    fn lower_repeats(input: &str, mode: RepeatMode) -> Workout {
+        let (tree, errors) = parse(input);
+        assert_eq!(errors, []);
+
+        let ast = ast::Workout::cast(tree.root(), &tree).unwrap();
+        let options = LowerOptions::default().with_repeat_mode(mode);
+        let (workout, errors) = Workout::lower_with(ast, &tree, &options);
+        assert_eq!(errors, []);
+
+        workout
+    }
+
+    fn pounds(amounts: &[f64]) -> Vec<Weight> {
+        amounts
+            .iter()
+            .map(|&a| Weight::Straight(Load::new(a, WeightUnit::Pound)))
+            .collect()
+    }
+
+    #[test]
+    fn lower_repeat_expand() {
+        let workout = lower_repeats(
+            "repeat 2 {\n# Squat\n225x5\nA1 # Dip\nbw x10\nA2 # Row\n135x10\n}\n# Curl\n30x12",
+            RepeatMode::Expand,
+        );
+        let exercises: Vec<_> = workout
+            .set_groups()
+            .filter_map(|sg| sg.exercise())
+            .collect();
+        assert_eq!(
+            exercises,
+            ["Squat", "Dip", "Row", "Squat", "Dip", "Row", "Curl"]
+        );
+
+        let workout = lower_repeats(
+            "# Squat\nrepeat 2 {\n225 x5\nrepeat 2 { 245 x3 }\n}\n185 x8",
+            RepeatMode::Expand,
+        );
+        assert_eq!(
+            weights(&workout),
+            pounds(&[225.0, 245.0, 245.0, 225.0, 245.0, 245.0, 185.0])
+        );
+        assert_eq!(workout.set_groups().next().unwrap().repeats(), []);
+    }
+
+    #[test]
+    fn lower_repeat_preserve() {
+        let workout = lower_repeats(
+            "repeat 2 {\n# Squat\n225x5\nA1 # Dip\nbw x10\nA2 # Row\n135x10\n}\n# Curl\n30x12",
+            RepeatMode::Preserve,
+        );
+        let [Block::Repeat(repeat), Block::SetGroup(curl)] = workout.blocks() else {
+            panic!("expected a repeat then a set group");
+        };
+        assert_eq!(repeat.count(), 2);
+        assert_eq!(repeat.blocks().len(), 2);
+        assert_eq!(curl.exercise(), Some("Curl"));
+        assert_eq!(workout.set_groups().count(), 4);
+
+        let workout = lower_repeats(
+            "# Squat\nrepeat 2 {\n225 x5\nrepeat 2 { 245 x3 }\n}\n185 x8",
+            RepeatMode::Preserve,
+        );
+        let squat = workout.set_groups().next().unwrap();
+        assert_eq!(weights(&workout), pounds(&[225.0, 245.0, 185.0]));
+        assert_eq!(
+            squat.repeats(),
+            [
+                SetRepeat {
+                    count: 2,
+                    sets: 1..2
+                },
+                SetRepeat {
+                    count: 2,
+                    sets: 0..2
+                },
+            ]
+        );
+        assert_eq!(squat.repetitions(1), 4);
+        assert_eq!(squat.repetitions(2), 1);
+        assert_eq!(
+            squat.volume(WeightUnit::Pound),
+            225.0 * 5.0 * 2.0 + 245.0 * 3.0 * 4.0 + 185.0 * 8.0
+        );
+    }
+
+    #[test]
+    fn lower_variations() {
+        let workout = lower(
+            "# Squat (paused, belt, SSB) [heavy]\n225x5\n# Squat\n315x3\n# Bench ( close grip, )\n185x5",
+            WeightUnit::Pound,
+        );
+
+        let groups: Vec<_> = workout.set_groups().collect();
+        assert_eq!(groups[0].exercise(), Some("Squat"));
+        assert_eq!(groups[0].variations(), ["paused", "belt", "SSB"]);
+        assert!(groups[0].has_tag("heavy"));
+        assert!(groups[1].variations().is_empty());
+        assert_eq!(groups[2].variations(), ["close grip"]);
+
+        let squats = groups.iter().filter(|sg| sg.is_variant_of("squat")).count();
+        assert_eq!(squats, 2);
+        assert!(!groups[2].is_variant_of("Squat"));
+    }
+
+    #[test]
+    fn lower_names_starting_with_keywords() {
+        let workout = lower(
+            "# Tm Press\n100x5\n# Amrap Pushups\nbw x20\n# Let me squat\n225x5\n# Repeat Row\n135x8\n# Include Row\n135x8\n# Kcal Burner\n2km 7:45",
+            WeightUnit::Pound,
+        );
+
+        let names: Vec<_> = workout
+            .set_groups()
+            .filter_map(|sg| sg.exercise())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "Tm Press",
+                "Amrap Pushups",
+                "Let me squat",
+                "Repeat Row",
+                "Include Row",
+                "Kcal Burner"
+            ]
+        );
+    }
+
+    #[test]
+    fn lower_tags() {
+        let workout = lower(
+            "# Squat [Heavy, comp]\n225x5\n# Curl [arms]\n30x12\n\n## 2026-10-17 Lower [legs, heavy]\n# Leg Press\n400x10\n\n## Upper\n# Bench [ comp ]\n185x5",
+            WeightUnit::Pound,
+        );
+
+        let squat = workout.set_groups().next().unwrap();
+        assert_eq!(squat.tags().iter().collect::<Vec<_>>(), ["comp", "heavy"]);
+        assert!(squat.has_tag("HEAVY"));
+        assert!(workout.sessions()[0].has_tag("legs"));
+
+        let names =
+            |tag| -> Vec<_> { workout.tagged(tag).filter_map(|sg| sg.exercise()).collect() };
+        assert_eq!(names("heavy"), ["Squat", "Leg Press"]);
+        assert_eq!(names("comp"), ["Squat", "Bench"]);
+        assert_eq!(names("arms"), ["Curl"]);
+        assert!(names("back").is_empty());
+    }
+
+    #[test]
+    fn lower_include_unresolved() {
+        let workout = lower("include \"warmup.wl\"\n# Squat\n225x5", WeightUnit::Pound);
+
+        let [Block::Include(include), Block::SetGroup(_)] = workout.blocks() else {
+            panic!("expected an include then a set group");
+        };
+        assert_eq!(include.path(), "warmup.wl");
+        assert_eq!(include.range(), range(0, 19));
+        assert_eq!(workout.set_groups().count(), 1);
+    }
+
+    #[test]
+    fn lower_repeat_too_many() {
+        let (workout, errors) = lower_with_errors(
+            "# Squat\nrepeat 3000000000 {\n225x5\n}\nrepeat 99999999999999999999999 {\n225x3\n}\nrepeat 1000 {\n135x1\n}",
+            WeightUnit::Pound,
+        );
+
+        let sets = workout.set_groups().next().unwrap().sets();
+        assert_eq!(sets.len(), 1002);
+        assert_eq!(
+            errors,
+            [
+                LowerError::new(range(15, 25), LowerErrorKind::InvalidRepeatCount),
+                LowerError::new(range(43, 66), LowerErrorKind::InvalidRepeatCount),
+            ]
+        );
+    }
+
+    #[test]
+    fn lower_repeat_nested_too_many() {
+        let (workout, errors) = lower_with_errors(
+            "# Squat\nrepeat 1000 {\nrepeat 1000 {\nrepeat 1000 {\n225x5\n}\n}\n}",
+            WeightUnit::Pound,
+        );
+
+        assert_eq!(workout.set_groups().next().unwrap().sets().len(), 1000);
+        assert_eq!(
+            errors,
+            [
+                LowerError::new(range(29, 33), LowerErrorKind::InvalidRepeatCount),
+                LowerError::new(range(43, 47), LowerErrorKind::InvalidRepeatCount),
+            ]
+        );
+
+        let (workout, errors) = lower_with_errors(
+            "repeat 100 {\nrepeat 10 {\n# Squat\n225x5\n}\nrepeat 11 {\n# Bench\n135x5\n}\n}",
+            WeightUnit::Pound,
+        );
 
-        Self::Duration(duration * multiplier)
+        assert_eq!(workout.set_groups().count(), 1100);
+        assert_eq!(
+            errors,
+            [LowerError::new(
+                range(48, 50),
+                LowerErrorKind::InvalidRepeatCount
+            )]
+        );
     }
 
-    fn lower_long_duration(long: ast::LongDuration, tree: &SyntaxTree) -> Self {
-        let hour = long.hour(tree).map(|h| h.parse(tree)).unwrap_or(0);
-        let minute = long.minute(tree).map(|m| m.parse(tree)).unwrap_or(0);
-        let second = long.second(tree).map(|s| s.parse(tree)).unwrap_or(0);
+    #[test]
+    fn lower_repeat_zero() {
+        let (workout, errors) =
+            lower_with_errors("# Squat\nrepeat 0 {\n225x5\n}", WeightUnit::Pound);
 
-        let duration = (hour * 3600) + (minute * 60) + second;
-        Self::Duration(duration)
+        assert_eq!(weights(&workout), []);
+        assert_eq!(
+            errors,
+            [LowerError::new(
+                range(15, 16),
+                LowerErrorKind::InvalidRepeatCount
+            )]
+        );
     }
 }
@@ -1,15 +1,24 @@
 use std::fmt::Display;
 
 use eventree::{TextRange, TextSize};
-use logos::{Lexer, Logos, Source};
+use logos::{Lexer, Logos};
 
 pub fn lex(input: &str) -> Vec<Token> {
     let mut lexer = TokenKind::lexer(input);
     let mut tokens = Vec::new();
     while let Some(kind) = lexer.next() {
+        let kind = match alias(lexer.slice()) {
+            Some((len, kind)) if len == lexer.slice().len() => kind,
+            _ => kind.unwrap_or(TokenKind::Error),
+        };
+        let kind = name(&mut lexer, kind);
+
         let span = lexer.span();
+
+        lexer.extras.push(kind);
+
         tokens.push(Token {
-            kind: kind.unwrap_or(TokenKind::Error),
+            kind,
             range: TextRange::new(
                 TextSize::new(span.start as u32),
                 TextSize::new(span.end as u32),
@@ -20,6 +29,137 @@ pub fn lex(input: &str) -> Vec<Token> {
     tokens
 }
 
+/// Keyword lexed again as the name it starts, ex `# Mile Run`, `let letter = 5` or `0.5*tmax`
+fn name(lexer: &mut Lexer<TokenKind>, kind: TokenKind) -> TokenKind {
+    if kind.is_trivia() || kind == TokenKind::Ident {
+        return kind;
+    }
+
+    let word = lexer.slice().starts_with(|c: char| c.is_ascii_alphabetic());
+    let is_name = match lexer.extras.previous {
+        // whatever follows `#` is the exercise name
+        Some(TokenKind::Hash) => true,
+        Some(TokenKind::Let) => word,
+        // keys of the front matter, ex `bodyweight` of `bodyweight: 185lb`
+        Some(TokenKind::Newline) if lexer.extras.front_matter == FrontMatter::Inside => word,
+        // a label only starts a line, ex `A1 # Bench`
+        Some(previous) if kind == TokenKind::Label => {
+            !matches!(previous, TokenKind::Newline | TokenKind::LBrace)
+        }
+        // only a keyword starting a longer word, `tm` of `0.65*tm` is still the training max
+        _ => {
+            lexer.extras.in_expression()
+                && word
+                && lexer
+                    .remainder()
+                    .starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        }
+    };
+    if !is_name {
+        return kind;
+    }
+
+    let len = ident_len(&lexer.source()[lexer.span().start..], &lexer.extras);
+    if len < lexer.slice().len() {
+        return kind;
+    }
+
+    lexer.bump(len - lexer.slice().len());
+    TokenKind::Ident
+}
+
+/// Other spellings from phone keyboards and copied logs, lexed as the kind
+/// they stand for, the tree keeps the text as written.
+///
+/// `*` isn't one of them, only the parser can tell `225*5` from `2*100 x5`
+const ALIASES: &[(&str, TokenKind)] = &[
+    ("×", TokenKind::X),
+    ("reps", TokenKind::X),
+    ("bodyweight", TokenKind::Bodyweight),
+];
+
+/// length and kind of the alias `text` starts with, a word only when it ends
+/// there, ex `reps` of `reps 5` or `reps5` but not of `repsol`
+fn alias(text: &str) -> Option<(usize, TokenKind)> {
+    ALIASES.iter().find_map(|&(alias, kind)| {
+        let rest = text
+            .get(..alias.len())
+            .filter(|start| start.eq_ignore_ascii_case(alias))
+            .map(|_| &text[alias.len()..])?;
+        let word = alias.starts_with(|c: char| c.is_ascii_alphabetic());
+        (!word || !rest.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_'))
+            .then_some((alias.len(), kind))
+    })
+}
+
+/// What came before the token being lexed, so `ident` knows where a name ends
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LexContext {
+    /// last token that isn't a space or comment
+    previous: Option<TokenKind>,
+    front_matter: FrontMatter,
+    /// `#` or `##` starting the line
+    heading: Option<TokenKind>,
+    /// within the `[..]` of tags on a heading
+    tags: bool,
+    /// within the `(..)` of modifiers on an exercise
+    modifiers: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum FrontMatter {
+    /// nothing but newlines yet, a `---` opens the front matter
+    #[default]
+    Allowed,
+    Inside,
+    Closed,
+}
+
+impl LexContext {
+    fn push(&mut self, kind: TokenKind) {
+        match kind {
+            TokenKind::Space | TokenKind::Comment => return,
+            TokenKind::Newline => {
+                self.heading = None;
+                self.tags = false;
+                self.modifiers = false;
+            }
+            TokenKind::Hash | TokenKind::DoubleHash => self.heading = Some(kind),
+            TokenKind::LBracket if self.heading.is_some() => self.tags = true,
+            TokenKind::RBracket => self.tags = false,
+            TokenKind::LParen if self.heading == Some(TokenKind::Hash) && !self.tags => {
+                self.modifiers = true
+            }
+            TokenKind::RParen => self.modifiers = false,
+            _ => {}
+        }
+
+        self.front_matter = match (self.front_matter, kind) {
+            (FrontMatter::Allowed, TokenKind::Fence) => FrontMatter::Inside,
+            (FrontMatter::Inside, TokenKind::Fence) => FrontMatter::Closed,
+            (FrontMatter::Allowed, TokenKind::Newline) => FrontMatter::Allowed,
+            (FrontMatter::Allowed, _) => FrontMatter::Closed,
+            (front_matter, _) => front_matter,
+        };
+        self.previous = Some(kind);
+    }
+
+    /// after an operator, where a name is a binding
+    fn in_expression(&self) -> bool {
+        self.previous.is_some_and(|kind| {
+            matches!(
+                kind,
+                TokenKind::Equals
+                    | TokenKind::Star
+                    | TokenKind::Slash
+                    | TokenKind::Plus
+                    | TokenKind::Minus
+                    | TokenKind::At
+            ) || (kind == TokenKind::LParen && !self.modifiers)
+        })
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct Token {
     pub kind: TokenKind,
@@ -35,17 +175,56 @@ impl Display for Token {
 #[derive(Logos, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
+#[logos(extras = LexContext)]
 pub enum TokenKind {
+    /// warm-up set marker, ex `w 135x5` or `(w) 135x5`
+    #[token("w", ignore(ascii_case))]
+    #[token("(w)", ignore(ascii_case))]
+    Warmup,
     #[token("bw", ignore(ascii_case))]
     Bodyweight,
     #[token("x", ignore(ascii_case))]
     X,
+    #[token("amrap", ignore(ascii_case))]
+    Amrap,
+    /// failed the rep after the ones logged, ex `x5F`
+    #[token("f", ignore(ascii_case))]
+    Fail,
     #[token("+")]
     Plus,
+    #[token("-")]
+    Minus,
+    #[token(">")]
+    Greater,
+    #[token("*")]
+    Star,
+    #[token("/")]
+    Slash,
+    /// reps done on each side, ex `x8/side` or `x8 each`
+    #[token("/side", ignore(ascii_case))]
+    #[token("each", ignore(ascii_case))]
+    PerSide,
+    #[token("@")]
+    At,
+    #[token("rpe", ignore(ascii_case))]
+    Rpe,
+    #[token("rir", ignore(ascii_case))]
+    Rir,
+
+    #[token("kg", ignore(ascii_case))]
+    #[token("kgs", ignore(ascii_case))]
+    Kilogram,
+    #[token("lb", ignore(ascii_case))]
+    #[token("lbs", ignore(ascii_case))]
+    Pound,
+    #[token("%")]
+    Percent,
+    #[token("tm", ignore(ascii_case))]
+    Tm,
 
     #[regex("[0-9]+")]
     Integer,
-    #[regex(r#"([1-9][0-9]*|0)?\.[0-9]*"#)]
+    #[regex(r#"([1-9][0-9]*|0)\.[0-9]*|\.[0-9]+"#)]
     Float,
 
     #[token("h", ignore(ascii_case))]
@@ -54,18 +233,84 @@ pub enum TokenKind {
     Minute,
     #[token("s", ignore(ascii_case))]
     Second,
+
+    // a bare `m` is lexed as minutes, the parser reads it as meters next to a load
+    // or another time, ex `70kg 40m` or `500m 1:45`
+    #[token("meter", ignore(ascii_case))]
+    #[token("meters", ignore(ascii_case))]
+    #[token("metre", ignore(ascii_case))]
+    #[token("metres", ignore(ascii_case))]
+    Meter,
+    #[token("km", ignore(ascii_case))]
+    Kilometer,
+    #[token("yd", ignore(ascii_case))]
+    #[token("yds", ignore(ascii_case))]
+    Yard,
+    #[token("ft", ignore(ascii_case))]
+    Foot,
+    #[token("mi", ignore(ascii_case))]
+    #[token("mile", ignore(ascii_case))]
+    #[token("miles", ignore(ascii_case))]
+    Mile,
+    #[token("cal", ignore(ascii_case))]
+    #[token("kcal", ignore(ascii_case))]
+    Calorie,
+    /// average heart rate in beats per minute, ex `hr142`
+    #[regex("[hH][rR][0-9]+")]
+    HeartRate,
     #[token(":")]
     Colon,
+    #[token("=")]
+    Equals,
+    #[token("(")]
+    LParen,
+    #[token(")")]
+    RParen,
+    #[token("let", ignore(ascii_case))]
+    Let,
+    /// block done a number of times, ex `repeat 3 {`
+    #[token("repeat", ignore(ascii_case))]
+    Repeat,
+    #[token("{")]
+    LBrace,
+    #[token("}")]
+    RBrace,
+    /// tags on an exercise or session, ex `# Squat [heavy, comp]`
+    #[token("[")]
+    LBracket,
+    #[token("]")]
+    RBracket,
+    /// blocks of another document, ex `include "warmup.wl"`
+    #[token("include", ignore(ascii_case))]
+    Include,
+
+    #[regex(r#""[^"\n]*""#)]
+    Text,
+
+    /// eccentric, bottom, concentric and top phases, ex `3-1-1-0` or `t:31X0`
+    #[regex("[0-9xX](-[0-9xX]){3}")]
+    #[regex("[tT]:[0-9xX]{4}")]
+    Tempo,
 
     #[token("#")]
     Hash,
+    /// session heading, ex `## 2026-10-17 Upper A`
+    #[token("##")]
+    DoubleHash,
+    // `X` is left out so `X5` is still reps
+    #[regex("[A-WYZ][0-9]+")]
+    Label,
+    #[token("---")]
+    Fence,
     #[token(",")]
     Comma,
     #[regex("\n+")]
     Newline,
     #[regex("[ \t]+")]
     Space,
-    #[regex("[hHmMsSxD][a-zA-Z]", ident)]
+    #[regex("//[^\n]*")]
+    Comment,
+    #[regex("[hHmMsSxDfFwW][a-zA-Z]", ident)]
     #[regex("[a-zA-Z]", ident)]
     Ident,
 
@@ -79,23 +324,57 @@ impl TokenKind {
     pub fn is_whitespace(self) -> bool {
         matches!(self, TokenKind::Space | TokenKind::Newline)
     }
+
+    /// tokens that carry no meaning and can appear between any others
+    pub fn is_trivia(self) -> bool {
+        self.is_whitespace() || self == TokenKind::Comment
+    }
 }
 
 fn ident(lex: &mut Lexer<TokenKind>) {
-    let mut last_significant = 0;
-    let remaining = lex.remainder();
+    let text = &lex.source()[lex.span().start..];
+    // an alias is a token of its own, `lex` looks up its kind, ex `reps` of `225 reps 5`
+    let len = match alias(text) {
+        Some((len, _)) => len,
+        None => ident_len(text, &lex.extras),
+    };
+    lex.bump(len.saturating_sub(lex.slice().len()));
+}
 
-    for (i, c) in remaining.char_indices() {
-        if matches!(c, ',' | '\n') {
+/// length of the ident `text` starts with
+fn ident_len(text: &str, context: &LexContext) -> usize {
+    // names in expressions are a single word, ex `tm` of `0.65*tm x5` or `let tm = 315`
+    if context.previous == Some(TokenKind::Let) || context.in_expression() {
+        return text
+            .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .unwrap_or(text.len());
+    }
+
+    let mut last_significant = 0;
+    for (i, c) in text.char_indices() {
+        let end = match c {
+            ',' | '\n' | '"' => true,
+            _ if text[i..].starts_with("//") => true,
+            // only keys of the front matter, ex `title: Upper A`
+            ':' => context.front_matter == FrontMatter::Inside,
+            // tags after a heading, ex `# Squat [heavy]` or `## Lower [legs]`
+            '[' => context.heading.is_some() && !context.tags,
+            ']' => context.tags,
+            // modifiers after an exercise name, ex `# Squat (paused, belt)`
+            '(' => context.heading == Some(TokenKind::Hash) && !context.modifiers && !context.tags,
+            ')' => context.modifiers,
+            _ => false,
+        };
+        if end {
             break;
         }
 
         if !matches!(c, ' ' | '\t') {
-            last_significant = remaining.find_boundary(i + 1);
+            last_significant = i + c.len_utf8();
         }
     }
 
-    lex.bump(last_significant);
+    last_significant
 }
 
 #[cfg(test)]
@@ -104,8 +383,7 @@ mod tests {
     use TokenKind::*;
 
     fn lex_kind(input: &str) -> Vec<TokenKind> {
-        let lex = TokenKind::lexer(input);
-        lex.into_iter().map(Result::unwrap).collect()
+        lex(input).into_iter().map(|token| token.kind).collect()
     }
 
     #[test]
@@ -137,17 +415,177 @@ bw 30s
         assert_eq!(lex_kind("BW"), [Bodyweight]);
     }
 
+    #[test]
+    fn lex_warmup() {
+        assert_eq!(lex_kind("w"), [Warmup]);
+        assert_eq!(lex_kind("(W)"), [Warmup]);
+        assert_eq!(lex_kind("w 135x5"), [Warmup, Space, Integer, X, Integer]);
+
+        assert_eq!(lex_kind("Wide Grip Bench"), [Ident]);
+    }
+
     #[test]
     fn lex_x() {
         assert_eq!(lex_kind("x"), [X]);
         assert_eq!(lex_kind("X"), [X]);
     }
 
+    #[test]
+    fn lex_aliases() {
+        for (alias, kind) in ALIASES {
+            let kinds = lex_kind(&format!("225{alias}5"));
+            assert_eq!(kinds, [Integer, *kind, Integer], "{alias}");
+        }
+
+        assert_eq!(lex_kind("3 × 5"), [Integer, Space, X, Space, Integer]);
+        assert_eq!(lex_kind("225 REPS 5"), [Integer, Space, X, Space, Integer]);
+        assert_eq!(lex_kind("Bodyweight x5"), [Bodyweight, Space, X, Integer]);
+        assert_eq!(lex_kind("bodyweight*10"), [Bodyweight, Star, Integer]);
+        assert_eq!(lex_kind("repsol"), [Ident]);
+
+        let input = "3 × 5";
+        let x = lex(input)[2];
+        assert_eq!(&input[x.range], "×");
+    }
+
+    #[test]
+    fn lex_star() {
+        // reps or multiplication is up to the parser
+        assert_eq!(lex_kind("225*5"), [Integer, Star, Integer]);
+        assert_eq!(lex_kind("225kg*5"), [Integer, Kilogram, Star, Integer]);
+        assert_eq!(lex_kind("0.65*tm"), [Float, Star, Tm]);
+        assert_eq!(
+            lex_kind("2*100 x5"),
+            [Integer, Star, Integer, Space, X, Integer]
+        );
+    }
+
+    #[test]
+    fn lex_alias_starting_name() {
+        assert_eq!(lex_kind("# Bodyweight Squat"), [Hash, Space, Ident]);
+        assert_eq!(lex_kind("# Reps"), [Hash, Space, Ident]);
+        assert_eq!(lex_kind("# × Press"), [Hash, Space, Ident]);
+    }
+
+    #[test]
+    fn lex_amrap() {
+        assert_eq!(lex_kind("amrap"), [Amrap]);
+        assert_eq!(lex_kind("AMRAP"), [Amrap]);
+        assert_eq!(lex_kind("x5+"), [X, Integer, Plus]);
+    }
+
+    #[test]
+    fn lex_fail() {
+        assert_eq!(lex_kind("x5F"), [X, Integer, Fail]);
+        assert_eq!(lex_kind("x4/5"), [X, Integer, Slash, Integer]);
+
+        assert_eq!(lex_kind("Front Squat"), [Ident]);
+    }
+
+    #[test]
+    fn lex_per_side() {
+        assert_eq!(lex_kind("x8/side"), [X, Integer, PerSide]);
+        assert_eq!(lex_kind("x8 each"), [X, Integer, Space, PerSide]);
+        assert_eq!(lex_kind("x8/SIDE"), [X, Integer, PerSide]);
+
+        assert_eq!(lex_kind("Side Plank"), [Ident]);
+        assert_eq!(lex_kind("# Each Arm Row"), [Hash, Space, Ident]);
+    }
+
     #[test]
     fn lex_plus() {
         assert_eq!(lex_kind("+"), [Plus]);
     }
 
+    #[test]
+    fn lex_at() {
+        assert_eq!(lex_kind("@"), [At]);
+        assert_eq!(
+            lex_kind("5x5 @ 315"),
+            [Integer, X, Integer, Space, At, Space, Integer]
+        );
+    }
+
+    #[test]
+    fn lex_effort() {
+        assert_eq!(lex_kind("@8"), [At, Integer]);
+        assert_eq!(lex_kind("RPE 8.5"), [Rpe, Space, Float]);
+        assert_eq!(lex_kind("rir2"), [Rir, Integer]);
+    }
+
+    #[test]
+    fn lex_weight_units() {
+        assert_eq!(lex_kind("kg"), [Kilogram]);
+        assert_eq!(lex_kind("KGs"), [Kilogram]);
+
+        assert_eq!(lex_kind("lb"), [Pound]);
+        assert_eq!(lex_kind("LBS"), [Pound]);
+
+        assert_eq!(lex_kind("225lb"), [Integer, Pound]);
+        assert_eq!(
+            lex_kind("bw + 20kg"),
+            [Bodyweight, Space, Plus, Space, Integer, Kilogram]
+        );
+    }
+
+    #[test]
+    fn lex_percent() {
+        assert_eq!(lex_kind("%"), [Percent]);
+        assert_eq!(lex_kind("75% x5"), [Integer, Percent, Space, X, Integer]);
+    }
+
+    #[test]
+    fn lex_training_max() {
+        assert_eq!(lex_kind("tm"), [Tm]);
+        assert_eq!(lex_kind("TM 315lb"), [Tm, Space, Integer, Pound]);
+    }
+
+    #[test]
+    fn lex_minus() {
+        assert_eq!(lex_kind("-"), [Minus]);
+        assert_eq!(
+            lex_kind("2026-10-17"),
+            [Integer, Minus, Integer, Minus, Integer]
+        );
+    }
+
+    #[test]
+    fn lex_greater() {
+        assert_eq!(
+            lex_kind("225x8 > 185x6"),
+            [Integer, X, Integer, Space, Greater, Space, Integer, X, Integer]
+        );
+    }
+
+    #[test]
+    fn lex_fence() {
+        assert_eq!(lex_kind("---"), [Fence]);
+        assert_eq!(lex_kind("---\n"), [Fence, Newline]);
+    }
+
+    #[test]
+    fn lex_header_entry() {
+        assert_eq!(
+            lex_kind("---\ntitle: Upper A"),
+            [Fence, Newline, Ident, Colon, Space, Ident]
+        );
+        assert_eq!(
+            lex_kind("\n---\nbodyweight: 185lb\n---"),
+            [Newline, Fence, Newline, Ident, Colon, Space, Integer, Pound, Newline, Fence]
+        );
+
+        // colons only end keys inside the front matter
+        assert_eq!(lex_kind("# Bench: close grip"), [Hash, Space, Ident]);
+        assert_eq!(
+            lex_kind("---\n---\n# Bench: close grip"),
+            [Fence, Newline, Fence, Newline, Hash, Space, Ident]
+        );
+        assert_eq!(
+            lex_kind("# Squat\n---\ntitle: A"),
+            [Hash, Space, Ident, Newline, Fence, Newline, Ident]
+        );
+    }
+
     #[test]
     fn lex_time_units() {
         assert_eq!(lex_kind("h"), [Hour]);
@@ -160,6 +598,115 @@ bw 30s
         assert_eq!(lex_kind("S"), [Second]);
     }
 
+    #[test]
+    fn lex_distance_units() {
+        assert_eq!(lex_kind("40meters"), [Integer, Meter]);
+        assert_eq!(lex_kind("Metre"), [Meter]);
+        assert_eq!(lex_kind("5km"), [Integer, Kilometer]);
+        assert_eq!(lex_kind("20yds"), [Integer, Yard]);
+        assert_eq!(lex_kind("100ft"), [Integer, Foot]);
+        assert_eq!(lex_kind("1.5mi"), [Float, Mile]);
+        assert_eq!(lex_kind("2 miles"), [Integer, Space, Mile]);
+
+        // minutes until the parser decides otherwise
+        assert_eq!(lex_kind("40m"), [Integer, Minute]);
+    }
+
+    #[test]
+    fn lex_ident_starting_with_distance_unit() {
+        assert_eq!(lex_kind("# Military Press"), [Hash, Space, Ident]);
+        assert_eq!(lex_kind("# Mixed Grip"), [Hash, Space, Ident]);
+        assert_eq!(lex_kind("# Mile Run"), [Hash, Space, Ident]);
+        assert_eq!(lex_kind("# Ft Press"), [Hash, Space, Ident]);
+        assert_eq!(lex_kind("#Km"), [Hash, Ident]);
+    }
+
+    #[test]
+    fn lex_tempo() {
+        assert_eq!(lex_kind("3-1-1-0"), [Tempo]);
+        assert_eq!(lex_kind("2-0-X-1"), [Tempo]);
+        assert_eq!(lex_kind("t:31X0"), [Tempo]);
+        assert_eq!(lex_kind("T:4010"), [Tempo]);
+
+        assert_eq!(lex_kind("8-12"), [Integer, Minus, Integer]);
+    }
+
+    #[test]
+    fn lex_expression() {
+        assert_eq!(
+            lex_kind("let heavy = 315"),
+            [Let, Space, Ident, Space, Equals, Space, Integer]
+        );
+        assert_eq!(
+            lex_kind("0.65*heavy x5"),
+            [Float, Star, Ident, Space, X, Integer]
+        );
+        assert_eq!(
+            lex_kind("(heavy - 20) / 2"),
+            [LParen, Ident, Space, Minus, Space, Integer, RParen, Space, Slash, Space, Integer]
+        );
+        assert_eq!(
+            lex_kind("tm - 20 x8"),
+            [Tm, Space, Minus, Space, Integer, Space, X, Integer]
+        );
+
+        // names are only single words after an operator
+        assert_eq!(lex_kind("# Push - ups"), [Hash, Space, Ident]);
+        assert_eq!(lex_kind("# Leg Press"), [Hash, Space, Ident]);
+        assert_eq!(lex_kind("# Lettuce"), [Hash, Space, Ident]);
+    }
+
+    #[test]
+    fn lex_repeat() {
+        assert_eq!(
+            lex_kind("repeat 3 {\n}"),
+            [Repeat, Space, Integer, Space, LBrace, Newline, RBrace]
+        );
+        assert_eq!(lex_kind("# Repeaters"), [Hash, Space, Ident]);
+    }
+
+    #[test]
+    fn lex_cardio() {
+        assert_eq!(
+            lex_kind("2km 7:45 2:05/500m 120cal hr142"),
+            [
+                Integer, Kilometer, Space, Integer, Colon, Integer, Space, Integer, Colon, Integer,
+                Slash, Integer, Minute, Space, Integer, Calorie, Space, HeartRate
+            ]
+        );
+        assert_eq!(lex_kind("90kcal"), [Integer, Calorie]);
+        assert_eq!(lex_kind("# Calf Raise"), [Hash, Space, Ident]);
+    }
+
+    #[test]
+    fn lex_tags() {
+        assert_eq!(
+            lex_kind("# Squat [heavy, high bar]"),
+            [Hash, Space, Ident, Space, LBracket, Ident, Comma, Space, Ident, RBracket]
+        );
+
+        // brackets only end names on a heading
+        assert_eq!(
+            lex_kind("---\ntitle: [A] B\n---"),
+            [Fence, Newline, Ident, Colon, Space, LBracket, Ident, Newline, Fence]
+        );
+    }
+
+    #[test]
+    fn lex_modifiers() {
+        assert_eq!(
+            lex_kind("# Squat (paused, high bar)"),
+            [Hash, Space, Ident, Space, LParen, Ident, Comma, Space, Ident, RParen]
+        );
+        assert_eq!(lex_kind("## Upper (heavy) day"), [DoubleHash, Space, Ident]);
+    }
+
+    #[test]
+    fn lex_include() {
+        assert_eq!(lex_kind("include \"warmup.wl\""), [Include, Space, Text]);
+        assert_eq!(lex_kind("# Includes"), [Hash, Space, Ident]);
+    }
+
     #[test]
     fn lex_colon() {
         assert_eq!(lex_kind(":"), [Colon]);
@@ -178,6 +725,7 @@ bw 30s
         assert_eq!(lex_kind(".42"), [Float]);
         assert_eq!(lex_kind("42.46"), [Float]);
         assert_eq!(lex_kind("42."), [Float]);
+        assert_eq!(lex_kind("."), [Error]);
     }
 
     #[test]
@@ -185,6 +733,30 @@ bw 30s
         assert_eq!(lex_kind("#"), [Hash])
     }
 
+    #[test]
+    fn lex_double_hash() {
+        assert_eq!(
+            lex_kind("## 2026-10-17 Upper A"),
+            [DoubleHash, Space, Integer, Minus, Integer, Minus, Integer, Space, Ident]
+        );
+    }
+
+    #[test]
+    fn lex_label() {
+        assert_eq!(lex_kind("A1"), [Label]);
+        assert_eq!(lex_kind("C12 # Row"), [Label, Space, Hash, Space, Ident]);
+        assert_eq!(
+            lex_kind("\n  B2 # Row"),
+            [Newline, Space, Label, Space, Hash, Space, Ident]
+        );
+        assert_eq!(lex_kind("# B2 Bench"), [Hash, Space, Ident]);
+        assert_eq!(
+            lex_kind("---\ntitle: A1 Day"),
+            [Fence, Newline, Ident, Colon, Space, Ident]
+        );
+        assert_eq!(lex_kind("X5"), [X, Integer]);
+    }
+
     #[test]
     fn lex_comma() {
         assert_eq!(lex_kind(","), [Comma])
@@ -202,6 +774,37 @@ bw 30s
         assert_eq!(lex_kind("         "), [Space]);
     }
 
+    #[test]
+    fn lex_comment() {
+        assert_eq!(lex_kind("// heavy day"), [Comment]);
+        assert_eq!(lex_kind("// heavy\n"), [Comment, Newline]);
+        assert_eq!(
+            lex_kind("225 x5 // belt"),
+            [Integer, Space, X, Integer, Space, Comment]
+        );
+    }
+
+    #[test]
+    fn lex_ident_before_comment() {
+        assert_eq!(lex_kind("Bench Press // comp"), [Ident, Space, Comment]);
+        assert_eq!(lex_kind("Bench Press//comp"), [Ident, Comment]);
+    }
+
+    #[test]
+    fn lex_text() {
+        assert_eq!(lex_kind("\"belt on\""), [Text]);
+        assert_eq!(lex_kind("\"\""), [Text]);
+        assert_eq!(
+            lex_kind("225 x5 \"belt\""),
+            [Integer, Space, X, Integer, Space, Text]
+        );
+    }
+
+    #[test]
+    fn lex_ident_before_text() {
+        assert_eq!(lex_kind("Squat \"low bar\""), [Ident, Space, Text]);
+    }
+
     #[test]
     fn lex_ident() {
         assert_eq!(lex_kind("Bench Press"), [Ident]);
@@ -212,7 +815,29 @@ bw 30s
     #[test]
     fn lex_ident_starting_with_keyword() {
         assert_eq!(lex_kind("Squat"), [Ident]);
-        assert_eq!(lex_kind("# Squat"), [Hash, Space, Ident])
+        assert_eq!(lex_kind("# Squat"), [Hash, Space, Ident]);
+
+        for name in [
+            "# Tm Press",
+            "# Amrap Pushups",
+            "# Let me squat",
+            "# Repeat Row",
+            "# Include Row",
+            "# Kcal Burner",
+            "# B2 Bench",
+        ] {
+            assert_eq!(lex_kind(name), [Hash, Space, Ident], "{name}");
+        }
+
+        assert_eq!(
+            lex_kind("let letter = 0.5*tmax"),
+            [Let, Space, Ident, Space, Equals, Space, Float, Star, Ident]
+        );
+        assert_eq!(
+            lex_kind("let tm = 315"),
+            [Let, Space, Ident, Space, Equals, Space, Integer]
+        );
+        assert_eq!(lex_kind("@rpe8"), [At, Rpe, Integer]);
     }
 
     #[test]
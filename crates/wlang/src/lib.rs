@@ -3,6 +3,7 @@ pub mod autocomplete;
 pub mod hir;
 pub mod lexer;
 pub mod parser;
+pub mod resolve;
 pub mod utils;
 
 pub use eventree::{TextLen, TextRange, TextSize};
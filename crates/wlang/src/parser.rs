@@ -20,14 +20,14 @@ struct MarkClosed {
     index: usize,
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ParseError {
     token_idx: usize,
     kind: ParseErrorKind,
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ParseErrorKind {
     Expected(TokenKind),
@@ -66,7 +66,8 @@ impl ParseError {
     }
 }
 
-struct Parser {
+struct Parser<'i> {
+    input: &'i str,
     tokens: Vec<Token>,
     pos: usize,
     fuel: Cell<u32>,
@@ -76,15 +77,16 @@ struct Parser {
 
 pub fn parse(input: &str) -> (SyntaxTree, Vec<ParseError>) {
     let tokens = lex(input);
-    let mut p = Parser::new(tokens);
+    let mut p = Parser::new(input, tokens);
     workout(&mut p);
 
-    p.build_tree(input)
+    p.build_tree()
 }
 
-impl Parser {
-    pub fn new(tokens: Vec<Token>) -> Self {
+impl<'i> Parser<'i> {
+    pub fn new(input: &'i str, tokens: Vec<Token>) -> Self {
         Self {
+            input,
             tokens,
             pos: 0,
             fuel: Cell::new(256),
@@ -143,6 +145,13 @@ impl Parser {
             .map_or(TokenKind::Eof, |it| it.kind)
     }
 
+    /// source text of the token at lookahead, empty past the end
+    fn nth_text(&self, lookahead: usize) -> &'i str {
+        self.tokens
+            .get(self.pos + lookahead)
+            .map_or("", |it| &self.input[it.range])
+    }
+
     /// check next token
     fn at(&self, kind: TokenKind) -> bool {
         self.nth(0) == kind
@@ -218,7 +227,8 @@ impl Parser {
         self.close(m, NodeKind::Error);
     }
 
-    const WHITESPACE: TokenSet = TokenSet::from_array([TokenKind::Space, TokenKind::Newline]);
+    const WHITESPACE: TokenSet =
+        TokenSet::from_array([TokenKind::Space, TokenKind::Newline, TokenKind::Comment]);
 
     fn eat_ws(&mut self) {
         while self.at_any(Self::WHITESPACE) {
@@ -226,11 +236,17 @@ impl Parser {
         }
     }
 
-    fn build_tree(self, input: &str) -> (SyntaxTree, Vec<ParseError>) {
+    /// spaces and a comment before the end of a line
+    fn eat_trailing(&mut self) {
+        self.eat(TokenKind::Space);
+        self.eat(TokenKind::Comment);
+    }
+
+    fn build_tree(self) -> (SyntaxTree, Vec<ParseError>) {
         let mut tokens = self.tokens.into_iter();
         let errors = self.errors;
 
-        let mut builder = SyntaxBuilder::new(input);
+        let mut builder = SyntaxBuilder::new(self.input);
         for event in self.events {
             match event {
                 // Starting new node; just push empty tree to stack
@@ -257,12 +273,36 @@ impl Parser {
 
 fn workout(p: &mut Parser) {
     let m = p.open();
+    let mut seen_set_group = false;
 
     while !p.eof() {
         p.eat_ws();
+        // newlines ending the document, ex after `include "warmup.wl"`, a document
+        // of nothing but newlines is still an error
+        if p.eof() && seen_set_group {
+            break;
+        }
 
         if p.at(TokenKind::Hash) {
+            seen_set_group = true;
             set_group(p)
+        } else if p.at(TokenKind::Label) {
+            seen_set_group = true;
+            superset(p)
+        } else if p.at(TokenKind::DoubleHash) {
+            seen_set_group = true;
+            session(p)
+        } else if p.at(TokenKind::Let) {
+            seen_set_group = true;
+            let_binding(p)
+        } else if p.at(TokenKind::Repeat) {
+            seen_set_group = true;
+            repeat(p, block)
+        } else if p.at(TokenKind::Include) {
+            seen_set_group = true;
+            include(p)
+        } else if p.at(TokenKind::Fence) && !seen_set_group {
+            header(p)
         } else {
             p.advance_with_error("expected a set group");
         }
@@ -271,265 +311,1764 @@ fn workout(p: &mut Parser) {
     p.close(m, NodeKind::Workout);
 }
 
-const SET_FIRST: TokenSet = WEIGHT_FIRST.with_kind(TokenKind::X);
-
-fn set_group(p: &mut Parser) {
-    assert!(p.at(TokenKind::Hash));
+/// dated heading followed by the set groups done that day, ex `## 2026-10-17 Upper A`
+fn session(p: &mut Parser) {
+    assert!(p.at(TokenKind::DoubleHash));
     let m = p.open();
 
-    let e = p.open();
-    p.expect(TokenKind::Hash);
+    p.expect(TokenKind::DoubleHash);
     p.eat(TokenKind::Space);
-    p.expect(TokenKind::Ident);
-    p.close(e, NodeKind::Exercise);
 
-    p.eat(TokenKind::Space);
-    p.expect(TokenKind::Newline);
+    if at_date(p) {
+        let date = p.open();
+        for _ in 0..5 {
+            p.advance();
+        }
+        p.close(date, NodeKind::SessionDate);
+        p.eat(TokenKind::Space);
+    }
 
-    while !p.at(TokenKind::Hash) && !p.eof() {
-        p.eat_ws();
+    if !p.at_any(TITLE_END) && !p.eof() {
+        let title = p.open();
+        while !p.at_any(TITLE_END) && !at_tags(p) && !p.eof() {
+            p.advance();
+        }
+        p.close(title, NodeKind::SessionTitle);
+    }
+    if at_tags(p) {
+        p.eat(TokenKind::Space);
+        tags(p);
+    }
+    p.eat_trailing();
 
-        if p.at_any(SET_FIRST) {
-            set(p);
+    loop {
+        p.eat_ws();
 
-            if !p.eof() {
-                p.expect(TokenKind::Newline);
-            }
-        } else {
+        if p.at(TokenKind::DoubleHash) || p.eof() {
             break;
         }
+
+        block(p);
     }
 
-    p.close(m, NodeKind::SetGroup);
+    p.close(m, NodeKind::Session);
 }
 
-const WEIGHT_FIRST: TokenSet =
-    TokenSet::from_array([TokenKind::Float, TokenKind::Integer, TokenKind::Bodyweight]);
+/// set group, superset or anything else that can sit between them
+fn block(p: &mut Parser) {
+    if p.at(TokenKind::Hash) {
+        set_group(p)
+    } else if p.at(TokenKind::Label) {
+        superset(p)
+    } else if p.at(TokenKind::Let) {
+        let_binding(p)
+    } else if p.at(TokenKind::Repeat) {
+        repeat(p, block)
+    } else if p.at(TokenKind::Include) {
+        include(p)
+    } else {
+        p.advance_with_error("expected a set group");
+    }
+}
 
-const QUANTITY_FIRST: TokenSet = TokenSet::from_array([TokenKind::Integer, TokenKind::X]);
-const QUANTITY_END: TokenSet = TokenSet::from_array([
-    TokenKind::Second,
-    TokenKind::Minute,
-    TokenKind::Hour,
-    TokenKind::Colon,
-    TokenKind::X,
-]);
+/// blocks of another document, ex `include "warmup.wl"`, resolved by [`crate::resolve`]
+fn include(p: &mut Parser) {
+    assert!(p.at(TokenKind::Include));
+    let m = p.open();
 
-fn set(p: &mut Parser) {
-    assert!(p.at_any(SET_FIRST));
+    p.expect(TokenKind::Include);
+    p.eat(TokenKind::Space);
+    p.expect_and_skip_till(TokenKind::Text, HEADER_VALUE_END);
+    p.eat_trailing();
+
+    p.close(m, NodeKind::Include);
+}
+
+/// body done a number of times, ex `repeat 3 {` then set groups or sets then `}`
+fn repeat(p: &mut Parser, body: fn(&mut Parser)) {
+    assert!(p.at(TokenKind::Repeat));
     let m = p.open();
 
-    // if weight is followed by a token that can end a quantity
-    if p.at_any(WEIGHT_FIRST) && !QUANTITY_END.is_set(p.nth(1)) {
-        // weight then quantity
-        weight(p);
+    p.expect(TokenKind::Repeat);
+    p.eat(TokenKind::Space);
+    p.expect(TokenKind::Integer);
+    p.eat(TokenKind::Space);
+    p.expect(TokenKind::LBrace);
 
-        p.eat(TokenKind::Space);
+    loop {
+        p.eat_ws();
 
-        if p.at_any(QUANTITY_FIRST) {
-            quantity(p);
-        } else if !p.eof() {
-            p.advance_with_error("expected quantity");
+        if p.at(TokenKind::RBrace) || p.eof() {
+            break;
         }
-    } else if p.at_any(QUANTITY_FIRST) {
-        // quantity then weight
-        quantity(p);
 
-        p.eat(TokenKind::Space);
+        body(p);
+    }
 
-        if p.at_any(WEIGHT_FIRST) {
-            weight(p);
-        } else if !p.eof() {
-            p.advance_with_error("expected weight");
-        }
-    } else {
-        p.advance_with_error("expected set");
+    p.expect(TokenKind::RBrace);
+    p.eat_trailing();
+
+    p.close(m, NodeKind::Repeat);
+}
+
+/// repeat of set groups rather than sets, the first thing in the body is an exercise
+fn at_block_repeat(p: &Parser) -> bool {
+    let mut lookahead = 0;
+    while matches!(
+        p.nth(lookahead),
+        TokenKind::Repeat
+            | TokenKind::Integer
+            | TokenKind::LBrace
+            | TokenKind::Space
+            | TokenKind::Newline
+            | TokenKind::Comment
+    ) {
+        lookahead += 1;
     }
 
-    // consume trailing spaces
-    p.eat(TokenKind::Space);
+    SET_GROUP_FIRST.is_set(p.nth(lookahead))
+}
 
-    p.close(m, NodeKind::Set);
+const TITLE_END: TokenSet = HEADER_VALUE_END.with_kind(TokenKind::LBracket);
+
+/// tags after a name, the space before belongs to neither
+fn at_tags(p: &Parser) -> bool {
+    p.at(TokenKind::LBracket) || (p.at(TokenKind::Space) && p.nth(1) == TokenKind::LBracket)
 }
 
-fn weight(p: &mut Parser) {
-    assert!(p.at_any(WEIGHT_FIRST));
+/// comma separated labels to filter by, ex `[heavy, comp]`
+fn tags(p: &mut Parser) {
+    assert!(p.at(TokenKind::LBracket));
     let m = p.open();
 
-    p.eat_any(WEIGHT_FIRST);
+    p.expect(TokenKind::LBracket);
     p.eat(TokenKind::Space);
 
-    if p.at(TokenKind::Plus) {
-        p.eat(TokenKind::Plus);
-        p.eat(TokenKind::Space);
-        p.expect_any(WEIGHT_FIRST);
+    while !p.at_any(TAG_END) && !p.eof() {
+        let tag = p.open();
+        while !p.at_any(TAG_END.with_kind(TokenKind::Comma)) && !p.eof() {
+            p.advance();
+        }
+        p.close(tag, NodeKind::Tag);
+
+        if p.eat(TokenKind::Comma) {
+            p.eat(TokenKind::Space);
+        }
     }
 
-    p.close(m, NodeKind::Weight);
+    p.expect(TokenKind::RBracket);
+
+    p.close(m, NodeKind::Tags);
 }
 
-const SIMPLE_DURATION_UNIT: TokenSet =
-    TokenSet::from_array([TokenKind::Second, TokenKind::Minute, TokenKind::Hour]);
+const TAG_END: TokenSet = HEADER_VALUE_END.with_kind(TokenKind::RBracket);
 
-const REP_RECOVERY: TokenSet = TokenSet::from_array([TokenKind::Newline, TokenKind::Comma]);
+/// modifiers after an exercise name, the space before belongs to neither
+fn at_modifiers(p: &Parser) -> bool {
+    p.at(TokenKind::LParen) || (p.at(TokenKind::Space) && p.nth(1) == TokenKind::LParen)
+}
 
-fn quantity(p: &mut Parser) {
-    assert!(p.at_any(QUANTITY_FIRST));
+/// comma separated equipment and variations, ex `(paused, belt)`
+fn modifiers(p: &mut Parser) {
+    assert!(p.at(TokenKind::LParen));
     let m = p.open();
-    let mut typ = NodeKind::Reps;
 
-    if p.at(TokenKind::X) {
-        // rep prefix
-        p.eat(TokenKind::X);
-        p.expect_and_skip_till(TokenKind::Integer, REP_RECOVERY);
-    } else if p.at(TokenKind::Integer) {
-        // rep suffix
-        p.eat(TokenKind::Integer);
+    p.expect(TokenKind::LParen);
+    p.eat(TokenKind::Space);
 
-        if p.at(TokenKind::X) {
-            p.eat(TokenKind::X);
-        } else if p.at_any(SIMPLE_DURATION_UNIT) {
-            typ = NodeKind::SimpleDuration;
-            p.eat_any(SIMPLE_DURATION_UNIT);
-        } else if p.at(TokenKind::Colon) {
-            // seconds or minutes
-            typ = NodeKind::LongDuration;
-            p.eat(TokenKind::Colon);
-            p.expect(TokenKind::Integer);
+    while !p.at_any(MODIFIER_END) && !p.eof() {
+        let modifier = p.open();
+        while !p.at_any(MODIFIER_END.with_kind(TokenKind::Comma)) && !p.eof() {
+            p.advance();
+        }
+        p.close(modifier, NodeKind::Modifier);
 
-            // seconds
-            if p.at(TokenKind::Colon) {
-                p.eat(TokenKind::Colon);
-                p.expect(TokenKind::Integer);
-            }
+        if p.eat(TokenKind::Comma) {
+            p.eat(TokenKind::Space);
         }
     }
 
-    p.close(m, typ);
-}
+    p.expect(TokenKind::RParen);
 
-#[cfg(test)]
-mod tests {
+    p.close(m, NodeKind::Modifiers);
+}
 
-    use crate::ast::walker::{PlainPrinter, SyntaxNodeExt};
-    use crate::ast::SourceTree;
+const MODIFIER_END: TokenSet = HEADER_VALUE_END
+    .with_kind(TokenKind::RParen)
+    .with_kind(TokenKind::LBracket);
+
+/// `YYYY-MM-DD`, validated when lowering
+fn at_date(p: &Parser) -> bool {
+    p.at(TokenKind::Integer)
+        && p.nth(1) == TokenKind::Minus
+        && p.nth(2) == TokenKind::Integer
+        && p.nth(3) == TokenKind::Minus
+        && p.nth(4) == TokenKind::Integer
+}
 
-    use super::*;
+/// front matter of `key: value` lines between `---` fences
+fn header(p: &mut Parser) {
+    assert!(p.at(TokenKind::Fence));
+    let m = p.open();
 
-    macro_rules! parse_snapshot {
-        ($input:expr) => {{
-            parse_snapshot!($input, [])
-        }};
+    p.expect(TokenKind::Fence);
+    p.eat_trailing();
 
-        ($input:expr, $errors:expr) => {{
-            let tokens = crate::lexer::lex($input);
-            eprintln!("{tokens:#?}");
-            let (tree, errors) = parse($input);
-            let root = tree.root();
+    loop {
+        p.eat_ws();
 
-            // make sure print to same as input
-            let mut printer = PlainPrinter::default();
-            root.walk(&mut printer, &tree).unwrap();
-            assert_eq!($input, printer.take());
+        if p.at(TokenKind::Fence) || p.eof() {
+            break;
+        }
 
-            let source_tree = SourceTree::new(&tree);
-            insta::with_settings!({
-                description => $input,
-                omit_expression => true
-            }, {
-                insta::assert_debug_snapshot!(source_tree)
-            });
-            assert_eq!(errors, $errors);
-        }};
+        header_entry(p);
     }
 
-    #[test]
-    fn workout_simplest() {
-        parse_snapshot!(
-            "
-# Bench Press
-225 x5"
-        );
-    }
+    p.expect(TokenKind::Fence);
+    p.eat_trailing();
 
-    #[test]
-    fn workout_multiple_sets() {
-        parse_snapshot!(
-            "
-# Bench Press
-225 x5
-245 x8"
-        );
+    p.close(m, NodeKind::Header);
+}
+
+const HEADER_VALUE_END: TokenSet = TokenSet::from_array([TokenKind::Newline, TokenKind::Comment]);
+
+fn header_entry(p: &mut Parser) {
+    let m = p.open();
+
+    let key = p.open();
+    while !p.at_any(HEADER_VALUE_END.with_kind(TokenKind::Colon)) && !p.eof() {
+        p.advance();
     }
+    p.close(key, NodeKind::HeaderKey);
 
-    #[test]
-    fn workout_multiple_groups() {
-        parse_snapshot!(
-            "
-# Bench Press
-225 x5
-245.5 x8
+    p.expect(TokenKind::Colon);
+    p.eat(TokenKind::Space);
 
-# Pull-ups
-bw x5
-BW x10"
-        );
+    // values are free form, it's up to the consumer to interpret them
+    let value = p.open();
+    while !p.at_any(HEADER_VALUE_END) && !p.eof() {
+        p.advance();
     }
+    p.close(value, NodeKind::HeaderValue);
 
-    #[test]
-    fn workout_space_before_set_group() {
-        parse_snapshot!(
-            "
-        # Bench Press
-225 x5
+    p.eat(TokenKind::Comment);
 
-    # Pull-ups
-bw x3"
-        );
+    p.close(m, NodeKind::HeaderEntry);
+}
+
+const SET_FIRST: TokenSet = WEIGHT_FIRST
+    .with_kind(TokenKind::X)
+    .with_kind(TokenKind::Amrap)
+    .with_kind(TokenKind::Warmup)
+    .with_kind(TokenKind::LParen);
+
+/// consecutive set groups labelled with the same letter, ex `A1 # Bench` then `A2 # Row`
+fn superset(p: &mut Parser) {
+    assert!(p.at(TokenKind::Label));
+    let m = p.open();
+
+    let letter = p.nth_text(0).chars().next();
+    while p.at(TokenKind::Label) && p.nth_text(0).chars().next() == letter {
+        set_group(p);
     }
 
-    #[test]
-    fn workout_space_before_set() {
-        parse_snapshot!(
-            "
-# Bench Press
-        225 x5
+    p.close(m, NodeKind::Superset);
+}
 
-    245 x8"
-        );
+/// tokens after the last set of a line
+const SET_END: TokenSet = TokenSet::from_array([
+    TokenKind::Newline,
+    TokenKind::Comma,
+    TokenKind::Comment,
+    TokenKind::RBrace,
+]);
+
+const SET_GROUP_FIRST: TokenSet = TokenSet::from_array([TokenKind::Hash, TokenKind::Label]);
+
+fn set_group(p: &mut Parser) {
+    assert!(p.at_any(SET_GROUP_FIRST));
+    let m = p.open();
+
+    if p.eat(TokenKind::Label) {
+        p.eat(TokenKind::Space);
     }
 
-    #[test]
-    fn workout_space_after_set() {
-        parse_snapshot!(
-            "
-# Bench Press
-225 x5      
-245 x8      "
-        );
+    let e = p.open();
+    p.expect(TokenKind::Hash);
+    p.eat(TokenKind::Space);
+    p.expect(TokenKind::Ident);
+    if at_modifiers(p) {
+        p.eat(TokenKind::Space);
+        modifiers(p);
+    }
+    if at_tags(p) {
+        p.eat(TokenKind::Space);
+        tags(p);
     }
+    p.close(e, NodeKind::Exercise);
 
-    #[test]
-    fn workout_reps_x_suffix_and_prefix() {
-        parse_snapshot!("#Bench Press\n225 x5");
-        parse_snapshot!("#Bench Press\n225 5x");
+    p.eat(TokenKind::Space);
+    if p.at(TokenKind::Text) {
+        note(p);
     }
 
-    #[test]
-    fn workout_set_weight_and_quantity_any_order() {
-        parse_snapshot!("#Bench Press\n225 x5");
-        parse_snapshot!("#Bench Press\nx5 225");
+    p.eat_trailing();
+    p.expect(TokenKind::Newline);
 
-        parse_snapshot!("#Bench Press\n5x 225");
+    let mut seen_set = false;
+    while !p.at(TokenKind::Hash) && !p.eof() {
+        p.eat_ws();
 
-        parse_snapshot!("#Bench Press\nbw 30s");
-        parse_snapshot!("#Bench Press\n30s bw");
+        // a `let` after an empty line is for the set groups below, ex `let heavy = 315`
+        // written above the next exercise
+        if !at_set_line(p) || (p.at(TokenKind::Let) && after_blank_line(p)) {
+            break;
+        }
 
-        parse_snapshot!("#Bench Press\nbw 1:30");
-        parse_snapshot!("#Bench Press\n1:30 bw");
+        // the training max only goes directly under the exercise, so a `tm 300` meant
+        // for the next one isn't taken by this one
+        let training_max = p.at(TokenKind::Tm) && !at_set(p);
+        if training_max && seen_set {
+            let m = p.open();
+            p.errors.push(ParseError::custom(
+                p.pos,
+                String::from("expected training max before sets"),
+            ));
+            set_line(p);
+            p.close(m, NodeKind::Error);
+        } else {
+            seen_set |= !training_max && !p.at(TokenKind::Let);
+            set_line(p);
+        }
+    }
 
-        parse_snapshot!("#Pull-ups\nbw + 10 x10");
-        parse_snapshot!("#Pull-ups\n10x bw + 10");
+    p.close(m, NodeKind::SetGroup);
+}
+
+/// an empty line comes before the current line, comment lines in between are
+/// skipped, ex `225 x5\n\n// top\nlet`
+fn after_blank_line(p: &Parser) -> bool {
+    let mut newlines = 0;
+    for token in p.tokens[..p.pos].iter().rev() {
+        match token.kind {
+            TokenKind::Newline => newlines += p.input[token.range].len(),
+            TokenKind::Comment => newlines = 0,
+            TokenKind::Space => {}
+            _ => break,
+        }
+        if newlines > 1 {
+            return true;
+        }
+    }
+
+    false
+}
+
+fn at_set_line(p: &Parser) -> bool {
+    at_set(p)
+        || at_cardio(p)
+        || p.at_any(TokenSet::from_array([TokenKind::Tm, TokenKind::Let]))
+        || (p.at(TokenKind::Repeat) && !at_block_repeat(p))
+}
+
+/// sets, training max or binding on a line of a set group
+fn set_line(p: &mut Parser) {
+    if at_set(p) || at_cardio(p) {
+        any_set(p);
+
+        // more sets on the same line, ex `225x5, 245x3`
+        while p.eat(TokenKind::Comma) {
+            p.eat(TokenKind::Space);
+            if at_set(p) || at_cardio(p) {
+                any_set(p);
+            } else {
+                p.errors.push(ParseError::custom(
+                    p.pos,
+                    String::from("expected set after comma"),
+                ));
+                break;
+            }
+        }
+    } else if p.at(TokenKind::Tm) {
+        training_max(p);
+    } else if p.at(TokenKind::Let) {
+        let_binding(p);
+    } else if p.at(TokenKind::Repeat) {
+        repeat(p, set_line);
+    } else {
+        p.advance_with_error("expected a set");
+        return;
+    }
+
+    if !p.at(TokenKind::RBrace) && !p.eof() {
+        p.expect(TokenKind::Newline);
+    }
+}
+
+fn any_set(p: &mut Parser) {
+    // checked first, `2km 10:00` would otherwise be a distance then a weight
+    if at_cardio(p) {
+        cardio_set(p);
+    } else {
+        set(p);
+    }
+}
+
+/// start of a set, `tm` only when used in an expression, ex `tm - 20 x8` rather than `tm 315`
+fn at_set(p: &Parser) -> bool {
+    if p.at(TokenKind::Tm) {
+        let next = if p.nth(1) == TokenKind::Space { 2 } else { 1 };
+        return BINARY_OP.with_kind(TokenKind::X).is_set(p.nth(next));
+    }
+
+    p.at_any(SET_FIRST)
+}
+
+/// named value for expressions, ex `let tm = 315`
+fn let_binding(p: &mut Parser) {
+    assert!(p.at(TokenKind::Let));
+    let m = p.open();
+
+    p.expect(TokenKind::Let);
+    p.eat(TokenKind::Space);
+
+    if p.at_any(NAME) {
+        let name = p.open();
+        p.advance();
+        p.close(name, NodeKind::Name);
+    } else {
+        p.errors.push(ParseError::expected_one_of(p.pos, NAME));
+    }
+
+    p.eat(TokenKind::Space);
+    p.expect(TokenKind::Equals);
+    p.eat(TokenKind::Space);
+
+    if p.at_any(EXPR_FIRST) {
+        expr(p);
+        weight_unit(p);
+    } else {
+        p.errors
+            .push(ParseError::expected_one_of(p.pos, EXPR_FIRST));
+    }
+
+    p.eat_trailing();
+
+    p.close(m, NodeKind::Let);
+}
+
+/// training max percentages in the set group are based on, ex `tm 315` or `tm: 140kg`
+fn training_max(p: &mut Parser) {
+    assert!(p.at(TokenKind::Tm));
+    let m = p.open();
+
+    p.expect(TokenKind::Tm);
+    p.eat(TokenKind::Space);
+    if p.eat(TokenKind::Colon) {
+        p.eat(TokenKind::Space);
+    }
+
+    if p.at_any(WEIGHT_FIRST) {
+        weight(p);
+    } else {
+        p.errors
+            .push(ParseError::expected_one_of(p.pos, WEIGHT_FIRST));
+    }
+
+    p.eat_trailing();
+
+    p.close(m, NodeKind::TrainingMax);
+}
+
+const WEIGHT_FIRST: TokenSet =
+    TokenSet::from_array([TokenKind::Float, TokenKind::Integer, TokenKind::Bodyweight]);
+
+const QUANTITY_FIRST: TokenSet = TokenSet::from_array([
+    TokenKind::Integer,
+    TokenKind::Float,
+    TokenKind::X,
+    TokenKind::Amrap,
+]);
+const QUANTITY_END: TokenSet = TokenSet::from_array([
+    TokenKind::Second,
+    TokenKind::Minute,
+    TokenKind::Hour,
+    TokenKind::Colon,
+    TokenKind::X,
+    TokenKind::Meter,
+    TokenKind::Kilometer,
+    TokenKind::Yard,
+    TokenKind::Foot,
+    TokenKind::Mile,
+]);
+
+/// metrics only measured in conditioning work, or more than one metric, so a
+/// lone `5km` or `1:00` is still a regular set
+fn at_cardio(p: &Parser) -> bool {
+    match metric(p, 0) {
+        Some((len, Metric::Distance | Metric::Duration)) => {
+            p.nth(len) == TokenKind::Space && metric(p, len + 1).is_some()
+        }
+        Some(_) => true,
+        None => false,
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Metric {
+    Distance,
+    Duration,
+    Pace,
+    Calories,
+    HeartRate,
+}
+
+/// tokens taken by the metric at lookahead
+fn metric(p: &Parser, lookahead: usize) -> Option<(usize, Metric)> {
+    let nth = |n| p.nth(lookahead + n);
+
+    if nth(0) == TokenKind::HeartRate {
+        return Some((1, Metric::HeartRate));
+    }
+    if nth(0) == TokenKind::Integer && nth(1) == TokenKind::Calorie {
+        return Some((2, Metric::Calories));
+    }
+    if NUMBER.is_set(nth(0)) && DISTANCE_UNIT.is_set(nth(1)) {
+        return Some((2, Metric::Distance));
+    }
+
+    let duration = match (nth(0), nth(1), nth(2), nth(3), nth(4)) {
+        (TokenKind::Integer, unit, ..) if SIMPLE_DURATION_UNIT.is_set(unit) => 2,
+        (
+            TokenKind::Integer,
+            TokenKind::Colon,
+            TokenKind::Integer,
+            TokenKind::Colon,
+            TokenKind::Integer,
+        ) => 5,
+        (TokenKind::Integer, TokenKind::Colon, TokenKind::Integer, ..) => 3,
+        _ => return None,
+    };
+
+    // pace, ex `2:05/500m` or `5:30/km`
+    if nth(duration) == TokenKind::Slash {
+        let amount = usize::from(NUMBER.is_set(nth(duration + 1)));
+        if PACE_UNIT.is_set(nth(duration + 1 + amount)) {
+            return Some((duration + 2 + amount, Metric::Pace));
+        }
+    }
+
+    Some((duration, Metric::Duration))
+}
+
+/// a time per minute isn't a pace, so `m` is always meters here
+const PACE_UNIT: TokenSet = DISTANCE_UNIT.with_kind(TokenKind::Minute);
+
+/// whole number and a bare `m`, minutes unless read as [`meters`]
+fn at_bare_m(p: &Parser, lookahead: usize) -> bool {
+    p.nth(lookahead) == TokenKind::Integer && p.nth(lookahead + 1) == TokenKind::Minute
+}
+
+/// bare `m` read as a distance, ex `40m` of `70kg 40m` or `500m` of `500m 1:45`
+fn meters(p: &mut Parser) {
+    assert!(at_bare_m(p, 0));
+    let m = p.open();
+
+    p.expect(TokenKind::Integer);
+    p.expect(TokenKind::Minute);
+
+    p.close(m, NodeKind::Distance);
+}
+
+/// another time on the line makes a bare `m` meters, ex `500m 1:45`, alone it's
+/// minutes, ex `30m 5km`
+fn cardio_bare_m_is_meters(p: &Parser) -> bool {
+    let mut lookahead = 0;
+    while let Some((len, metric)) = metric(p, lookahead) {
+        if matches!(metric, Metric::Duration) && !at_bare_m(p, lookahead) {
+            return true;
+        }
+
+        lookahead += len;
+        if p.nth(lookahead) != TokenKind::Space {
+            break;
+        }
+        lookahead += 1;
+    }
+
+    false
+}
+
+/// conditioning work, ex `2km 7:45 2:05/500m 120cal hr142 "steady"`
+fn cardio_set(p: &mut Parser) {
+    assert!(at_cardio(p));
+    let m = p.open();
+    let bare_m_is_meters = cardio_bare_m_is_meters(p);
+    let mut seen = Vec::new();
+
+    while let Some((_, mut metric_kind)) = metric(p, 0) {
+        if metric_kind == Metric::Duration && bare_m_is_meters && at_bare_m(p, 0) {
+            metric_kind = Metric::Distance;
+        }
+
+        // only one of each is kept, ex the second time of `500m 1:45 1:50`
+        if seen.contains(&metric_kind) {
+            p.errors.push(ParseError::custom(
+                p.pos,
+                String::from("metric repeated in cardio set"),
+            ));
+        }
+        seen.push(metric_kind);
+
+        match metric_kind {
+            Metric::Distance if at_bare_m(p, 0) => meters(p),
+            Metric::HeartRate => p.advance(),
+            Metric::Calories => {
+                let calories = p.open();
+                p.advance();
+                p.advance();
+                p.close(calories, NodeKind::Calories);
+            }
+            Metric::Pace => pace(p),
+            Metric::Distance | Metric::Duration => quantity(p),
+        }
+
+        if p.at(TokenKind::Space) && metric(p, 1).is_some() {
+            p.eat(TokenKind::Space);
+        } else {
+            break;
+        }
+    }
+
+    p.eat(TokenKind::Space);
+
+    if p.at_any(EFFORT_FIRST) {
+        effort(p);
+        p.eat(TokenKind::Space);
+    }
+
+    if p.at(TokenKind::Text) {
+        note(p);
+    }
+
+    p.eat_trailing();
+
+    p.close(m, NodeKind::CardioSet);
+}
+
+/// time per distance, ex `2:05/500m` or `5:30/km`
+fn pace(p: &mut Parser) {
+    let m = p.open();
+
+    quantity(p);
+    p.expect(TokenKind::Slash);
+    p.eat_any(NUMBER);
+    p.expect_any(PACE_UNIT);
+
+    p.close(m, NodeKind::Pace);
+}
+
+fn set(p: &mut Parser) {
+    assert!(at_set(p));
+    let m = p.open();
+
+    if p.eat(TokenKind::Warmup) {
+        p.eat(TokenKind::Space);
+
+        // the marker still needs a set after it, ex `w 135x5` but not `w Squat`
+        if !p.at_any(WEIGHT_START.union(QUANTITY_FIRST)) {
+            if p.at(TokenKind::Newline) || p.eof() {
+                p.errors
+                    .push(ParseError::custom(p.pos, String::from("expected set")));
+            } else {
+                p.advance_with_error("expected set");
+            }
+            p.eat_trailing();
+            p.close(m, NodeKind::Set);
+            return;
+        }
+    }
+
+    // `225x5` is always 225 for 5 reps, whatever markers follow, ex `225x8+5` or
+    // `225x8 > 185x6`, unless a weight follows, `3x5 225` is 3 sets of 5, a count
+    // followed by the unit of a quantity is the quantity, ex `5x 225`, `30s` or `5 reps`,
+    // a name can only start the weight when it's the training max, ex `tm*0.8 x5`
+    let weight_first = (p.at_any(NAME.with_kind(TokenKind::LParen)) && p.at_any(WEIGHT_START))
+        || (p.at_any(WEIGHT_FIRST)
+            && !at_range(p)
+            && if at_x(p, 1) {
+                !p.at(TokenKind::Integer) || (p.nth(2) == TokenKind::Integer && !at_sets_across(p))
+            } else {
+                !QUANTITY_END.is_set(p.nth(1)) && !at_rep_word_suffix(p, 0)
+            });
+
+    if weight_first {
+        // weight then quantity, a bare `m` after a load is meters, ex `70kg 40m`
+        let loaded = !p.at(TokenKind::Bodyweight);
+        weight(p);
+
+        p.eat(TokenKind::Space);
+
+        if at_set_count(p) {
+            set_count(p);
+        }
+
+        if loaded && at_bare_m(p, 0) {
+            meters(p);
+        } else if p.at_any(QUANTITY_FIRST) || at_x(p, 0) {
+            quantity(p);
+        } else if !p.eof() {
+            p.advance_with_error("expected quantity");
+        }
+    } else if p.at_any(QUANTITY_FIRST) {
+        // quantity then weight
+        if at_sets_across(p) {
+            set_count(p);
+        }
+
+        // a bare `m` before a load is meters, ex `40m 70kg`
+        if at_bare_m(p, 0) && p.nth(2) == TokenKind::Space && NUMBER.is_set(p.nth(3)) {
+            meters(p);
+        } else {
+            quantity(p);
+        }
+
+        p.eat(TokenKind::Space);
+
+        // `@` before any weight is the weight, so only `rpe`/`rir`, a tempo, a note or
+        // the end of the set can skip it
+        if p.at_any(WEIGHT_START) {
+            weight(p);
+        } else if !p.at_any(WEIGHT_OPTIONAL_FOLLOW.union(SET_END)) && !p.eof() {
+            p.advance_with_error("expected weight");
+        }
+    } else {
+        p.advance_with_error("expected set");
+    }
+
+    p.eat(TokenKind::Space);
+
+    if p.at(TokenKind::Tempo) {
+        tempo(p);
+        p.eat(TokenKind::Space);
+    }
+
+    if p.at_any(EFFORT_FIRST) {
+        effort(p);
+        p.eat(TokenKind::Space);
+    }
+
+    if p.at(TokenKind::Text) {
+        note(p);
+    }
+
+    p.eat(TokenKind::Space);
+    if p.at(TokenKind::Greater) {
+        drop_set(p);
+    }
+
+    // consume trailing spaces and comment
+    p.eat_trailing();
+
+    p.close(m, NodeKind::Set);
+}
+
+/// sets prefix of sets across, ex the `3` of `3x5`
+fn at_set_count(p: &Parser) -> bool {
+    p.at(TokenKind::Integer) && at_x(p, 1) && p.nth(2) == TokenKind::Integer
+}
+
+/// sets across followed by a weight, ex `3x5 225`, `5x5 @ 315`, `3x8-12 135` or `2x8+5 30`
+fn at_sets_across(p: &Parser) -> bool {
+    if !at_set_count(p) {
+        return false;
+    }
+
+    // skip the rest of a rep range, rest-pause or rep markers
+    let mut end = 3;
+    while matches!(
+        p.nth(end),
+        TokenKind::Minus | TokenKind::Plus | TokenKind::Slash
+    ) && p.nth(end + 1) == TokenKind::Integer
+    {
+        end += 2;
+    }
+    if matches!(p.nth(end), TokenKind::Plus | TokenKind::Fail) {
+        end += 1;
+    }
+    if p.nth(end) == TokenKind::PerSide {
+        end += 1;
+    } else if p.nth(end) == TokenKind::Space && p.nth(end + 1) == TokenKind::PerSide {
+        end += 2;
+    }
+
+    WEIGHT_START.is_set(p.nth(end))
+        || (p.nth(end) == TokenKind::Space && WEIGHT_START.is_set(p.nth(end + 1)))
+}
+
+/// `x` or a `*` standing for it at lookahead
+fn at_x(p: &Parser, lookahead: usize) -> bool {
+    p.nth(lookahead) == TokenKind::X || at_star_reps(p, lookahead)
+}
+
+/// `*` between a load and the reps, ex `225*5`, `225kg*5` or `bw * 10`, unless reps
+/// follow later in the set, ex `2*100 x5`, or it continues arithmetic, ex `@ 2*100`
+fn at_star_reps(p: &Parser, lookahead: usize) -> bool {
+    let reps = if p.nth(lookahead + 1) == TokenKind::Space {
+        lookahead + 2
+    } else {
+        lookahead + 1
+    };
+    if p.nth(lookahead) != TokenKind::Star || p.nth(reps) != TokenKind::Integer {
+        return false;
+    }
+
+    let star = p.pos + lookahead;
+    let significant = |token: &&Token| !matches!(token.kind, TokenKind::Space | TokenKind::Comment);
+    let mut before = p.tokens[..star]
+        .iter()
+        .rev()
+        .filter(significant)
+        .map(|t| t.kind);
+    let load = before.next();
+    let operand = before.next().is_some_and(|kind| {
+        matches!(
+            kind,
+            TokenKind::Plus
+                | TokenKind::Minus
+                | TokenKind::Star
+                | TokenKind::Slash
+                | TokenKind::LParen
+                | TokenKind::Equals
+                | TokenKind::At
+        )
+    });
+
+    let rest: Vec<_> = p.tokens[p.pos + reps + 1..]
+        .iter()
+        .filter(significant)
+        .map(|t| t.kind)
+        .take_while(|&kind| !SET_END.is_set(kind))
+        .collect();
+    // not a trailing `reps`, ex `225*5 reps`
+    let reps_follow = rest
+        .windows(2)
+        .any(|pair| pair == [TokenKind::X, TokenKind::Integer]);
+
+    load.is_some_and(|kind| WEIGHT_FIRST.union(WEIGHT_UNIT).is_set(kind))
+        && !operand
+        && !reps_follow
+}
+
+/// range of reps or seconds, ex `8-12x` or `30-45s`
+fn at_range(p: &Parser) -> bool {
+    p.at(TokenKind::Integer)
+        && p.nth(1) == TokenKind::Minus
+        && p.nth(2) == TokenKind::Integer
+        && RANGE_UNIT.is_set(p.nth(3))
+}
+
+fn set_count(p: &mut Parser) {
+    assert!(at_set_count(p));
+    let m = p.open();
+
+    p.expect(TokenKind::Integer);
+
+    p.close(m, NodeKind::SetCount);
+}
+
+const EFFORT_FIRST: TokenSet =
+    TokenSet::from_array([TokenKind::At, TokenKind::Rpe, TokenKind::Rir]);
+const NUMBER: TokenSet = TokenSet::from_array([TokenKind::Float, TokenKind::Integer]);
+
+/// rpe or rir after a set, ex `@8`, `rpe 8.5` or `rir2`
+fn effort(p: &mut Parser) {
+    assert!(p.at_any(EFFORT_FIRST));
+    let m = p.open();
+
+    p.eat_any(EFFORT_FIRST);
+    p.eat(TokenKind::Space);
+    p.expect_any(NUMBER);
+
+    p.close(m, NodeKind::Effort);
+}
+
+/// lighter set done right after the previous one, ex `> 185x6` of `225x8 > 185x6`
+fn drop_set(p: &mut Parser) {
+    assert!(p.at(TokenKind::Greater));
+    let m = p.open();
+
+    p.expect(TokenKind::Greater);
+    p.eat(TokenKind::Space);
+
+    if p.at_any(SET_FIRST) {
+        set(p);
+    } else {
+        p.errors
+            .push(ParseError::custom(p.pos, String::from("expected drop set")));
+    }
+
+    p.close(m, NodeKind::DropSet);
+}
+
+/// seconds in each phase of a rep, ex `3-1-1-0`
+fn tempo(p: &mut Parser) {
+    assert!(p.at(TokenKind::Tempo));
+    let m = p.open();
+
+    p.expect(TokenKind::Tempo);
+
+    p.close(m, NodeKind::Tempo);
+}
+
+/// quoted text trailing a set or exercise, ex `"belt on"`
+fn note(p: &mut Parser) {
+    assert!(p.at(TokenKind::Text));
+    let m = p.open();
+
+    p.expect(TokenKind::Text);
+
+    p.close(m, NodeKind::Note);
+}
+
+const WEIGHT_OPTIONAL_FOLLOW: TokenSet = EFFORT_FIRST
+    .with_kind(TokenKind::Tempo)
+    .with_kind(TokenKind::Text);
+
+const WEIGHT_START: TokenSet = WEIGHT_FIRST
+    .with_kind(TokenKind::At)
+    .with_kind(TokenKind::Tm)
+    .with_kind(TokenKind::LParen);
+const WEIGHT_OPERATOR: TokenSet = TokenSet::from_array([TokenKind::Plus, TokenKind::Minus]);
+const WEIGHT_UNIT: TokenSet = TokenSet::from_array([TokenKind::Kilogram, TokenKind::Pound]);
+
+fn weight(p: &mut Parser) {
+    assert!(p.at_any(WEIGHT_START));
+    let m = p.open();
+
+    if p.eat(TokenKind::At) {
+        p.eat(TokenKind::Space);
+    }
+
+    let bodyweight = p.at(TokenKind::Bodyweight);
+    if at_expression(p) {
+        expr(p);
+    } else {
+        p.expect_any(WEIGHT_FIRST);
+    }
+    if !p.eat(TokenKind::Percent) {
+        weight_unit(p);
+    }
+    p.eat(TokenKind::Space);
+
+    // added or assisted bodyweight, ex `bw + 45` or `bw - 30`, arithmetic on a
+    // load keeps the unit last, ex `225 - 10kg` rather than `225kg - 10kg`
+    if p.at_any(WEIGHT_OPERATOR) {
+        if !bodyweight {
+            p.errors.push(ParseError::custom(
+                p.pos,
+                String::from("expected the unit after the arithmetic"),
+            ));
+        }
+        p.eat_any(WEIGHT_OPERATOR);
+        p.eat(TokenKind::Space);
+        if p.expect_any(WEIGHT_FIRST) {
+            weight_unit(p);
+        }
+    }
+
+    p.close(m, NodeKind::Weight);
+}
+
+const NAME: TokenSet = TokenSet::from_array([TokenKind::Ident, TokenKind::Tm]);
+const EXPR_FIRST: TokenSet = NUMBER.union(NAME).with_kind(TokenKind::LParen);
+const BINARY_OP: TokenSet = TokenSet::from_array([
+    TokenKind::Plus,
+    TokenKind::Minus,
+    TokenKind::Star,
+    TokenKind::Slash,
+]);
+
+/// weight computed from names and arithmetic, ex `0.65*tm`, `tm - 20` or `315 - 20`
+fn at_expression(p: &Parser) -> bool {
+    p.at_any(NAME.with_kind(TokenKind::LParen))
+        || (p.at_any(NUMBER)
+            && binary_op_ahead(p, 1)
+            && !at_star_reps(p, if p.nth(1) == TokenKind::Space { 2 } else { 1 }))
+}
+
+/// operator followed by an operand, spaces allowed around it, so `bw + 45` and
+/// `x8+5` aren't arithmetic
+fn binary_op_ahead(p: &Parser, lookahead: usize) -> bool {
+    let op = if p.nth(lookahead) == TokenKind::Space {
+        lookahead + 1
+    } else {
+        lookahead
+    };
+    let operand = if p.nth(op + 1) == TokenKind::Space {
+        op + 2
+    } else {
+        op + 1
+    };
+
+    BINARY_OP.is_set(p.nth(op)) && EXPR_FIRST.is_set(p.nth(operand))
+}
+
+fn binding_power(op: TokenKind) -> Option<(u8, u8)> {
+    match op {
+        TokenKind::Plus | TokenKind::Minus => Some((1, 2)),
+        TokenKind::Star | TokenKind::Slash => Some((3, 4)),
+        _ => None,
+    }
+}
+
+fn expr(p: &mut Parser) {
+    expr_rec(p, 0);
+}
+
+fn expr_rec(p: &mut Parser, min_bp: u8) {
+    let Some(mut lhs) = expr_delimited(p) else {
+        return;
+    };
+
+    while binary_op_ahead(p, 0) {
+        let op = if p.at(TokenKind::Space) {
+            p.nth(1)
+        } else {
+            p.nth(0)
+        };
+        let Some((l_bp, r_bp)) = binding_power(op) else {
+            break;
+        };
+        if l_bp < min_bp {
+            break;
+        }
+
+        let m = p.open_before(lhs);
+        p.eat(TokenKind::Space);
+        p.expect(op);
+        p.eat(TokenKind::Space);
+        expr_rec(p, r_bp);
+        lhs = p.close(m, NodeKind::BinaryExpr);
+    }
+}
+
+fn expr_delimited(p: &mut Parser) -> Option<MarkClosed> {
+    if !p.at_any(EXPR_FIRST) {
+        p.errors
+            .push(ParseError::expected_one_of(p.pos, EXPR_FIRST));
+        return None;
+    }
+
+    let m = p.open();
+    let kind = if p.at(TokenKind::LParen) {
+        p.expect(TokenKind::LParen);
+        p.eat(TokenKind::Space);
+        expr_rec(p, 0);
+        p.eat(TokenKind::Space);
+        p.expect(TokenKind::RParen);
+        NodeKind::ParenExpr
+    } else if p.at_any(NAME) {
+        p.advance();
+        NodeKind::NameRef
+    } else {
+        p.advance();
+        NodeKind::Literal
+    };
+
+    Some(p.close(m, kind))
+}
+
+/// optional unit directly after a weight literal, ex `225lb` or `100 kg`
+fn weight_unit(p: &mut Parser) {
+    if p.at(TokenKind::Space) && WEIGHT_UNIT.is_set(p.nth(1)) {
+        p.eat(TokenKind::Space);
+    }
+
+    p.eat_any(WEIGHT_UNIT);
+}
+
+const SIMPLE_DURATION_UNIT: TokenSet =
+    TokenSet::from_array([TokenKind::Second, TokenKind::Minute, TokenKind::Hour]);
+
+const RANGE_UNIT: TokenSet = SIMPLE_DURATION_UNIT.with_kind(TokenKind::X);
+
+const DISTANCE_UNIT: TokenSet = TokenSet::from_array([
+    TokenKind::Meter,
+    TokenKind::Kilometer,
+    TokenKind::Yard,
+    TokenKind::Foot,
+    TokenKind::Mile,
+]);
+
+const REP_RECOVERY: TokenSet = TokenSet::from_array([TokenKind::Newline, TokenKind::Comma]);
+const REP_SEPARATOR: TokenSet = TokenSet::from_array([TokenKind::X, TokenKind::Star]);
+
+fn quantity(p: &mut Parser) {
+    assert!(p.at_any(QUANTITY_FIRST) || at_x(p, 0));
+    let m = p.open();
+    let mut typ = NodeKind::Reps;
+
+    if at_x(p, 0) {
+        // rep prefix, spaced when spelled out, ex `225 reps 5`
+        p.eat_any(REP_SEPARATOR);
+        if p.at(TokenKind::Space) && p.nth(1) == TokenKind::Integer {
+            p.eat(TokenKind::Space);
+        }
+        p.expect_and_skip_till(TokenKind::Integer, REP_RECOVERY);
+
+        if p.at(TokenKind::Minus) && p.nth(1) == TokenKind::Integer {
+            typ = NodeKind::RepRange;
+            p.eat(TokenKind::Minus);
+            p.eat(TokenKind::Integer);
+            rep_word(p);
+        } else {
+            rep_word(p);
+            rep_markers(p);
+        }
+    } else if p.at(TokenKind::Amrap) {
+        p.eat(TokenKind::Amrap);
+    } else if at_range(p) {
+        // range suffix
+        typ = NodeKind::RepRange;
+        p.eat(TokenKind::Integer);
+        p.eat(TokenKind::Minus);
+        p.eat(TokenKind::Integer);
+        p.expect_any(RANGE_UNIT);
+    } else if p.at(TokenKind::Integer) {
+        // rep suffix, spaced when spelled out, ex `5 reps`
+        let spelled_out = at_rep_word_suffix(p, 0);
+        p.eat(TokenKind::Integer);
+
+        if spelled_out {
+            rep_word(p);
+            rep_markers(p);
+        } else if at_x(p, 0) {
+            p.eat_any(REP_SEPARATOR);
+        } else if p.at_any(SIMPLE_DURATION_UNIT) {
+            typ = NodeKind::SimpleDuration;
+            p.eat_any(SIMPLE_DURATION_UNIT);
+        } else if p.at_any(DISTANCE_UNIT) {
+            typ = NodeKind::Distance;
+            p.eat_any(DISTANCE_UNIT);
+        } else if p.at(TokenKind::Colon) {
+            // seconds or minutes
+            typ = NodeKind::LongDuration;
+            p.eat(TokenKind::Colon);
+            p.expect(TokenKind::Integer);
+
+            // seconds
+            if p.at(TokenKind::Colon) {
+                p.eat(TokenKind::Colon);
+                p.expect(TokenKind::Integer);
+            }
+        }
+    } else if p.at(TokenKind::Float) {
+        // only distances can be fractional
+        typ = NodeKind::Distance;
+        p.eat(TokenKind::Float);
+        p.expect_any(DISTANCE_UNIT);
+    }
+
+    let reps = p.close(m, typ);
+
+    // rest-pause, ex `x8+5+3`
+    if typ == NodeKind::Reps && at_rest_pause(p) {
+        let m = p.open_before(reps);
+
+        while at_rest_pause(p) {
+            p.eat(TokenKind::Plus);
+            let r = p.open();
+            p.eat(TokenKind::Integer);
+            p.close(r, NodeKind::Reps);
+        }
+
+        p.close(m, NodeKind::RestPause);
+    }
+}
+
+/// spelled out reps after the count, ex `reps` of `225 x 5 reps`
+fn rep_word(p: &mut Parser) {
+    let next = if p.at(TokenKind::Space) { 1 } else { 0 };
+    if at_rep_word(p, next) {
+        p.eat(TokenKind::Space);
+        p.eat(TokenKind::X);
+    }
+}
+
+fn at_rep_word(p: &Parser, lookahead: usize) -> bool {
+    p.nth(lookahead) == TokenKind::X && p.nth_text(lookahead).eq_ignore_ascii_case("reps")
+}
+
+/// count with the reps spelled out after it at lookahead, ex `5 reps` of `225 5 reps`,
+/// a count after the word makes it the prefix instead, ex `225 reps 5`
+fn at_rep_word_suffix(p: &Parser, lookahead: usize) -> bool {
+    let word = if p.nth(lookahead + 1) == TokenKind::Space {
+        lookahead + 2
+    } else {
+        lookahead + 1
+    };
+    let count = if p.nth(word + 1) == TokenKind::Space {
+        word + 2
+    } else {
+        word + 1
+    };
+
+    p.nth(lookahead) == TokenKind::Integer
+        && at_rep_word(p, word)
+        && p.nth(count) != TokenKind::Integer
+}
+
+/// target reps, how the set ended and whether reps are per side, ex `/5` of `x4/5`,
+/// `F` of `x5F`, `+` of `x5+` or `/side` of `x8/side`
+fn rep_markers(p: &mut Parser) {
+    if p.at(TokenKind::Slash) && p.nth(1) == TokenKind::Integer {
+        p.eat(TokenKind::Slash);
+        p.eat(TokenKind::Integer);
+    }
+
+    if !p.eat(TokenKind::Fail) && p.at(TokenKind::Plus) && p.nth(1) != TokenKind::Integer {
+        p.eat(TokenKind::Plus);
+    }
+
+    if p.at(TokenKind::Space) && p.nth(1) == TokenKind::PerSide {
+        p.eat(TokenKind::Space);
+    }
+    p.eat(TokenKind::PerSide);
+}
+
+fn at_rest_pause(p: &Parser) -> bool {
+    p.at(TokenKind::Plus) && p.nth(1) == TokenKind::Integer
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::ast::walker::{PlainPrinter, SyntaxNodeExt};
+    use crate::ast::SourceTree;
+
+    use super::*;
+
+    macro_rules! parse_snapshot {
+        ($input:expr) => {{
+            parse_snapshot!($input, [])
+        }};
+
+        ($input:expr, $errors:expr) => {{
+            let tokens = crate::lexer::lex($input);
+            eprintln!("{tokens:#?}");
+            let (tree, errors) = parse($input);
+            let root = tree.root();
+
+            // make sure print to same as input
+            let mut printer = PlainPrinter::default();
+            root.walk(&mut printer, &tree).unwrap();
+            assert_eq!($input, printer.take());
+
+            let source_tree = SourceTree::new(&tree);
+            insta::with_settings!({
+                description => $input,
+                omit_expression => true
+            }, {
+                insta::assert_debug_snapshot!(source_tree)
+            });
+            assert_eq!(errors, $errors);
+        }};
+    }
+
+    #[test]
+    fn workout_simplest() {
+        parse_snapshot!(
+            "
+# Bench Press
+225 x5"
+        );
+    }
+
+    #[test]
+    fn workout_multiple_sets() {
+        parse_snapshot!(
+            "
+# Bench Press
+225 x5
+245 x8"
+        );
+    }
+
+    #[test]
+    fn workout_multiple_groups() {
+        parse_snapshot!(
+            "
+# Bench Press
+225 x5
+245.5 x8
+
+# Pull-ups
+bw x5
+BW x10"
+        );
+    }
+
+    #[test]
+    fn workout_space_before_set_group() {
+        parse_snapshot!(
+            "
+        # Bench Press
+225 x5
+
+    # Pull-ups
+bw x3"
+        );
+    }
+
+    #[test]
+    fn workout_space_before_set() {
+        parse_snapshot!(
+            "
+# Bench Press
+        225 x5
+
+    245 x8"
+        );
+    }
+
+    #[test]
+    fn workout_space_after_set() {
+        parse_snapshot!(
+            "
+# Bench Press
+225 x5      
+245 x8      "
+        );
+    }
+
+    #[test]
+    fn workout_reps_x_suffix_and_prefix() {
+        parse_snapshot!("#Bench Press\n225 x5");
+        parse_snapshot!("#Bench Press\n225 5x");
+    }
+
+    #[test]
+    fn workout_set_weight_and_quantity_any_order() {
+        parse_snapshot!("#Bench Press\n225 x5");
+        parse_snapshot!("#Bench Press\nx5 225");
+
+        parse_snapshot!("#Bench Press\n5x 225");
+
+        parse_snapshot!("#Bench Press\nbw 30s");
+        parse_snapshot!("#Bench Press\n30s bw");
+
+        parse_snapshot!("#Bench Press\nbw 1:30");
+        parse_snapshot!("#Bench Press\n1:30 bw");
+
+        parse_snapshot!("#Pull-ups\nbw + 10 x10");
+        parse_snapshot!("#Pull-ups\n10x bw + 10");
+    }
+
+    #[test]
+    fn workout_assisted_bodyweight() {
+        parse_snapshot!("#Pull-ups\nbw - 30 x8");
+        parse_snapshot!("#Dips\nx10 bw-20kg");
+        parse_snapshot!(
+            "#Squat\n225kg - 10kg x5",
+            [ParseError::custom(
+                6,
+                String::from("expected the unit after the arithmetic")
+            )]
+        );
+    }
+
+    #[test]
+    fn workout_weight_units() {
+        parse_snapshot!("#Bench Press\n225lb x5");
+        parse_snapshot!("#Bench Press\n100 kg x5");
+        parse_snapshot!("#Bench Press\nx5 100kgs");
+        parse_snapshot!("#Pull-ups\nbw + 20kg x5");
+    }
+
+    #[test]
+    fn workout_sets_across() {
+        parse_snapshot!("#Squat\n3x5 225");
+        parse_snapshot!("#Squat\n5x5 @ 315");
+        parse_snapshot!("#Squat\n5x5 @315lb");
+        parse_snapshot!("#Squat\n225 3x5");
+        parse_snapshot!("#Squat\n225x5");
+    }
+
+    #[test]
+    fn workout_effort() {
+        parse_snapshot!("#Squat\n225 x5 @8");
+        parse_snapshot!("#Squat\n225 x5 rpe 8.5");
+        parse_snapshot!("#Squat\n3x5 225 @7.5");
+        parse_snapshot!("#Pull-ups\nx5 rir2");
+        parse_snapshot!("#Pull-ups\nx5 @ 45 rir1");
+        parse_snapshot!("#Squat\nx12 @8\n3x5 @ 25 @8\nx5 @8 rpe 7");
+    }
+
+    #[test]
+    fn workout_percentage() {
+        parse_snapshot!("#Squat\n75% x5");
+        parse_snapshot!("#Squat\n3x5 @ 72.5%");
+        parse_snapshot!("#Squat\ntm 315\n65%x5\n75% x5");
+        parse_snapshot!("#Squat\nTM: 140kg\n85% x3");
+    }
+
+    #[test]
+    fn workout_training_max_after_sets() {
+        parse_snapshot!(
+            "#Squat\n75% x5\ntm 300\n# Bench\n75% x5",
+            [ParseError::custom(
+                9,
+                String::from("expected training max before sets")
+            )]
+        );
+        parse_snapshot!("#Squat\nlet top = 5\n// from last block\ntm 315\n75% x5\ntm - 20 x8");
+    }
+
+    #[test]
+    fn workout_training_max_missing_weight() {
+        parse_snapshot!(
+            "#Squat\ntm\n75% x5",
+            [ParseError::expected_one_of(4, WEIGHT_FIRST)]
+        );
+    }
+
+    #[test]
+    fn workout_comments() {
+        parse_snapshot!("// push day\n# Bench Press\n225 x5");
+        parse_snapshot!("# Bench Press // comp grip\n225 x5 // belt\n245 x3 @9 //grindy");
+        parse_snapshot!("# Bench Press\n225 x5\n// top set\n245 x3");
+        parse_snapshot!("# Squat\ntm 315 // from last block\n75% x5");
+    }
+
+    #[test]
+    fn workout_notes() {
+        parse_snapshot!("# Squat \"low bar\"\n225 x5 \"belt on\"");
+        parse_snapshot!("# Squat\n225 x5 @8 \"felt pinchy in left shoulder\" // check next week");
+        parse_snapshot!("# Pull-ups\nx5 \"kipping\"");
+    }
+
+    #[test]
+    fn workout_tempo() {
+        parse_snapshot!("# Squat\n225x5 3-1-1-0 @8");
+        parse_snapshot!("# Pull-ups\nx8 t:31X0 \"slow\"");
+    }
+
+    #[test]
+    fn workout_rest_pause() {
+        parse_snapshot!("# Curl\n30 x8+5+3");
+        parse_snapshot!("# Pull-ups\nx6+3+2 bw @10");
+    }
+
+    #[test]
+    fn workout_amrap() {
+        parse_snapshot!("# Squat\n3x5+ 225\n245 amrap @9");
+        parse_snapshot!("# Pull-ups\namrap bw");
+    }
+
+    #[test]
+    fn workout_failed_reps() {
+        parse_snapshot!("# Bench\n225x4/5\n225x5F \"missed lockout\"");
+    }
+
+    #[test]
+    fn workout_per_side() {
+        parse_snapshot!("# Single Arm Row\n3x8/side 70");
+        parse_snapshot!("# Bulgarian Split Squat\n50 x10 each @8");
+    }
+
+    #[test]
+    fn workout_warmup() {
+        parse_snapshot!("# Squat\nw 135x5\n(w) 185x3\n225x5");
+    }
+
+    #[test]
+    fn workout_warmup_without_set() {
+        parse_snapshot!(
+            "# Squat\nw Squat",
+            [ParseError::custom(6, String::from("expected set"))]
+        );
+        parse_snapshot!(
+            "# Squat\n(w)Squat",
+            [ParseError::custom(5, String::from("expected set"))]
+        );
+        parse_snapshot!(
+            "# Squat\nw\n225x5",
+            [ParseError::custom(5, String::from("expected set"))]
+        );
+        parse_snapshot!(
+            "A1 w Squat",
+            [
+                ParseError::expected(2, TokenKind::Hash),
+                ParseError::expected(2, TokenKind::Ident),
+                ParseError::expected(2, TokenKind::Newline),
+                ParseError::custom(4, String::from("expected set")),
+            ]
+        );
+        parse_snapshot!(
+            "# (w)hr",
+            [
+                ParseError::expected(2, TokenKind::Ident),
+                ParseError::expected(2, TokenKind::Newline),
+                ParseError::custom(3, String::from("expected set")),
+            ]
+        );
+    }
+
+    #[test]
+    fn workout_drop_set() {
+        parse_snapshot!("# Curl\n225x8 > 185x6>135x8 rpe10 // ouch");
+        parse_snapshot!(
+            "# Curl\n225x8 >\n185x6",
+            [ParseError::custom(9, String::from("expected drop set"))]
+        );
+    }
+
+    #[test]
+    fn workout_expressions() {
+        parse_snapshot!("let tm = 315\n# Squat\n0.65*tm x5\ntm - 20 x8");
+        parse_snapshot!("# Bench\nlet heavy = (tm - 20) / 2\nx5 @heavy + 10 * 2\n315 - 20 x3");
+        parse_snapshot!("let tm = 140kg\n# Squat\n0.65*tm x5");
+    }
+
+    #[test]
+    fn workout_let_between_set_groups() {
+        parse_snapshot!("# Squat\n225 x5\n\nlet heavy = 315\n# Bench\nx5 @heavy");
+        parse_snapshot!(
+            "# Squat\nlet heavy = 315\n225 x5\n  \n// top\nlet top = 335\n# Bench\nx1 @top"
+        );
+        parse_snapshot!("# Squat\n225 x5\n// top\nlet top = 335\nx1 @top");
+    }
+
+    #[test]
+    fn workout_let_missing_value() {
+        let (_, errors) = parse("let heavy =\n# Squat\n225 x5");
+        assert_eq!(
+            errors,
+            [ParseError::expected_one_of(
+                5,
+                TokenSet::from_array([
+                    TokenKind::Integer,
+                    TokenKind::Float,
+                    TokenKind::Ident,
+                    TokenKind::Tm,
+                    TokenKind::LParen
+                ])
+            )]
+        );
+    }
+
+    #[test]
+    fn workout_repeat() {
+        parse_snapshot!(
+            "repeat 3 {\n# Squat\n225x5\nA1 # Dip\nbw x10\nA2 # Row\n135x10\n}\n# Curl\n30x12"
+        );
+        parse_snapshot!("# Squat\nrepeat 2 {\n225x5\nrepeat 2 { 245x3 }\n}\n185x8");
+    }
+
+    #[test]
+    fn workout_repeat_missing_brace() {
+        let (_, errors) = parse("# Squat\nrepeat 3\n225x5\n}");
+        assert_eq!(errors, [ParseError::expected(7, TokenKind::LBrace)]);
+    }
+
+    #[test]
+    fn workout_cardio() {
+        parse_snapshot!("# Row\n2km 7:45 2:05/500m 120cal hr142 rpe8 \"steady\"\n500meters 1:45, 500meters 1:50\n# Bike\nhr150 1:00:00\n5:30/km\n10km");
+    }
+
+    #[test]
+    fn workout_cardio_repeated_metric() {
+        parse_snapshot!(
+            "#Row\n500m 1:45 1:50",
+            [ParseError::custom(
+                10,
+                String::from("metric repeated in cardio set")
+            )]
+        );
+    }
+
+    #[test]
+    fn workout_alias_times() {
+        parse_snapshot!("# Squat\n225×5\n3×5 225\n225 × 5");
+    }
+
+    #[test]
+    fn workout_alias_star() {
+        parse_snapshot!("# Squat\n225*5\n225kg*5\n3*5 225\n225*5 reps");
+        // still multiplication in arithmetic
+        parse_snapshot!("# Squat\n2*100 x5\nx5 @ 2*100\n100+2*50 x3");
+    }
+
+    #[test]
+    fn workout_alias_reps() {
+        parse_snapshot!("# Squat\n225 reps 5\n5 reps\n225 5 reps\n225 x 5 reps");
+        parse_snapshot!("# Squat\nx8-12 reps 135\nx8 reps each 20\n225x5 reps");
+    }
+
+    #[test]
+    fn workout_alias_bodyweight() {
+        parse_snapshot!(
+            "# Bodyweight Squat\nbodyweight x20\nBodyweight*10\nBW + 25 reps 8\nBODYWEIGHT 8 reps"
+        );
+    }
+
+    #[test]
+    fn workout_modifiers() {
+        parse_snapshot!(
+            "# Squat (paused, belt, SSB) [heavy]\n225x5\n# Bench ( close grip )\n185x5"
+        );
+        parse_snapshot!(
+            "# Squat (paused\n225x5",
+            [ParseError::expected(6, TokenKind::RParen)]
+        );
+    }
+
+    #[test]
+    fn workout_tags() {
+        parse_snapshot!("# Squat [heavy, comp] \"belt\"\n225x5\n# Curl []\n30x12");
+        parse_snapshot!("## 2026-10-17 Lower [legs]\n# Squat [high bar]\n225x5\n## [deload]");
+    }
+
+    #[test]
+    fn workout_tags_unclosed() {
+        let (_, errors) = parse("# Squat [heavy\n225x5");
+        assert_eq!(errors, [ParseError::expected(6, TokenKind::RBracket)]);
+    }
+
+    #[test]
+    fn workout_include() {
+        parse_snapshot!(
+            "include \"warmup.wl\"\n# Squat\n225x5\n\n## Upper\ninclude \"accessories.wl\" // arms"
+        );
+    }
+
+    #[test]
+    fn workout_include_before_final_newline() {
+        let (_, errors) = parse("# Squat\n225x5\ninclude \"a.wl\"\n");
+        assert_eq!(errors, []);
+        let (_, errors) = parse("include \"a.wl\"\nlet top = 315\n\n");
+        assert_eq!(errors, []);
+    }
+
+    #[test]
+    fn workout_include_missing_path() {
+        let (_, errors) = parse("include warmup\n# Squat\n225x5");
+        assert_eq!(errors, [ParseError::expected(2, TokenKind::Text)]);
+    }
+
+    #[test]
+    fn workout_comma_separated_sets() {
+        parse_snapshot!("# Squat\n225x5, 245x3, 265x1\n275x1,265x1 rpe9");
+        parse_snapshot!("# Squat\n225x5 \"belt\", 245x3 // heavy");
+    }
+
+    #[test]
+    fn workout_comma_separated_sets_missing_set() {
+        let (_, errors) = parse("# Squat\n225x5,\n245x3");
+        assert_eq!(
+            errors,
+            [ParseError::custom(
+                8,
+                String::from("expected set after comma")
+            )]
+        );
+    }
+
+    #[test]
+    fn workout_header() {
+        parse_snapshot!(
+            "---
+date: 2026-10-17
+title: Upper A
+bodyweight: 185lb
+location: Home gym // garage
+---
+
+# Bench Press
+225 x5"
+        );
+    }
+
+    #[test]
+    fn workout_names_with_colons_and_brackets() {
+        parse_snapshot!("# Bench: close grip\n225 x5\n## Day 2: A [upper]\n# Row [rings]\n135x8");
+    }
+
+    #[test]
+    fn workout_header_missing_colon() {
+        parse_snapshot!(
+            "---\ntitle Upper A\n---\n# Bench Press\n225 x5",
+            [ParseError::expected(3, TokenKind::Colon)]
+        );
+    }
+
+    #[test]
+    fn workout_header_after_set_group() {
+        parse_snapshot!(
+            "# Bench Press\n225 x5\n---",
+            [ParseError::custom(9, String::from("expected a set group"))]
+        );
+    }
+
+    #[test]
+    fn workout_sessions() {
+        parse_snapshot!(
+            "---
+title: Week 1
+---
+## 2026-10-17 Upper A // felt good
+# Bench
+225 x5
+
+## 2026-10-19
+A1 # Squat
+315 x5
+A2 # Plank
+bw 1:00
+## Lower B
+# Deadlift
+405 x3"
+        );
+    }
+
+    #[test]
+    fn workout_superset() {
+        parse_snapshot!(
+            "A1 # Bench Press
+225 x5
+A2 # Pull-ups
+bw x8
+
+B1 # Curl
+30 x12
+B2 # Triceps Pushdown
+50 x12
+B3 # Face Pull
+40 x15
+
+# Plank
+bw 1:00"
+        );
+    }
+
+    #[test]
+    fn workout_distance() {
+        parse_snapshot!("#Farmers Carry\n70kg 40meters");
+        parse_snapshot!("#Sled Push\n20yds 180lb");
+        parse_snapshot!("#Run\n1.5mi bw");
+        parse_snapshot!("#Run\nbw 5km");
+        parse_snapshot!("#Planks\nbw 40m");
+        parse_snapshot!("#Farmers Carry\n70kg 40m");
+        parse_snapshot!("#Row\n500m 1:45");
+    }
+
+    #[test]
+    fn workout_rep_range() {
+        parse_snapshot!("#Bench\n185 x8-12");
+        parse_snapshot!("#Bench\n3x8-12 135");
+        parse_snapshot!("#Plank\n30-45s bw");
+        parse_snapshot!("#Curl\n30 8-12x");
     }
 
     #[test]
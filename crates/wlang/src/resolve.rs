@@ -0,0 +1,540 @@
+use std::{
+    collections::HashMap,
+    io,
+    path::{Component, Path, PathBuf},
+};
+
+use eventree::TextRange;
+
+use crate::{
+    ast::{self, AstNode},
+    hir::{self, Block, LowerError, LowerOptions},
+    parser::{parse, ParseError},
+};
+
+/// Most blocks includes can add while resolving a document, counting the blocks within
+/// repeats, so a document included many times at each level is reported rather than
+/// copied without bound
+pub const MAX_INCLUDED_BLOCKS: usize = 10_000;
+
+/// Where documents are read from, so includes can come from disk or from
+/// buffers held in memory by an editor
+pub trait FileSystem {
+    fn read_to_string(&self, path: &Path) -> io::Result<String>;
+}
+
+/// Reads documents from disk
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OsFileSystem;
+
+impl FileSystem for OsFileSystem {
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        std::fs::read_to_string(path)
+    }
+}
+
+impl FileSystem for HashMap<PathBuf, String> {
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        self.get(path)
+            .cloned()
+            .ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Diagnostic {
+    Parse(ParseError),
+    Lower(LowerError),
+    Include(IncludeError),
+}
+
+/// Problem with an included document, reported at the `include` pointing to it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IncludeError {
+    range: TextRange,
+    path: PathBuf,
+    kind: IncludeErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IncludeErrorKind {
+    Read(io::ErrorKind),
+    /// document is already being included further up
+    Cycle,
+    /// diagnostics within the included document, ranges are relative to it
+    Invalid(Vec<Diagnostic>),
+    /// blocks of the document would take the includes past [`MAX_INCLUDED_BLOCKS`]
+    TooLarge,
+}
+
+impl IncludeError {
+    pub fn new(range: TextRange, path: PathBuf, kind: IncludeErrorKind) -> Self {
+        Self { range, path, kind }
+    }
+
+    /// Range of the `include` in the including document
+    pub fn range(&self) -> TextRange {
+        self.range
+    }
+
+    /// Included document, relative to the one resolving started from
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn kind(&self) -> &IncludeErrorKind {
+        &self.kind
+    }
+}
+
+/// Lowers a document with every `include` replaced by the blocks of the
+/// included document
+pub struct Resolver<F> {
+    fs: F,
+    options: LowerOptions,
+}
+
+impl<F: FileSystem> Resolver<F> {
+    pub fn new(fs: F) -> Self {
+        Self {
+            fs,
+            options: LowerOptions::default(),
+        }
+    }
+
+    /// Options every document is lowered with
+    pub fn with_options(mut self, options: LowerOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Only fails when the document at `path` itself can't be read, problems
+    /// with included documents are diagnostics
+    pub fn resolve(&self, path: &Path) -> io::Result<(hir::Workout, Vec<Diagnostic>)> {
+        let path = normalize(path);
+        let input = self.fs.read_to_string(&path)?;
+
+        let mut resolution = Resolution {
+            stack: vec![path],
+            ..Resolution::default()
+        };
+        Ok(self.lower(&input, 1, &mut resolution))
+    }
+
+    /// Lower a document done `repeat_factor` times by the repeats around its include
+    fn lower(
+        &self,
+        input: &str,
+        repeat_factor: usize,
+        resolution: &mut Resolution,
+    ) -> (hir::Workout, Vec<Diagnostic>) {
+        let (tree, parse_errors) = parse(input);
+        let ast = ast::Workout::cast(tree.root(), &tree).unwrap();
+        let (mut workout, lower_errors) =
+            hir::Workout::lower_repeated(ast, &tree, &self.options, repeat_factor);
+
+        let mut diagnostics: Vec<_> = parse_errors
+            .into_iter()
+            .map(Diagnostic::Parse)
+            .chain(lower_errors.into_iter().map(Diagnostic::Lower))
+            .collect();
+
+        // expanded repeats copy the same include, only report it once
+        let mut included = HashMap::new();
+        self.resolve_blocks(
+            workout.blocks_mut(),
+            resolution,
+            &mut included,
+            &mut diagnostics,
+        );
+        for session in workout.sessions_mut() {
+            self.resolve_blocks(
+                session.blocks_mut(),
+                resolution,
+                &mut included,
+                &mut diagnostics,
+            );
+        }
+
+        (workout, diagnostics)
+    }
+
+    fn resolve_blocks(
+        &self,
+        blocks: &mut Vec<Block>,
+        resolution: &mut Resolution,
+        included: &mut HashMap<TextRange, Vec<Block>>,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        let mut resolved = Vec::with_capacity(blocks.len());
+        for block in blocks.drain(..) {
+            match block {
+                Block::Include(include) => {
+                    let blocks = included
+                        .entry(include.range())
+                        .or_insert_with(|| self.include(&include, resolution, diagnostics));
+
+                    let size = block_count(blocks);
+                    if resolution.included_blocks + size > MAX_INCLUDED_BLOCKS {
+                        diagnostics.push(Diagnostic::Include(IncludeError::new(
+                            include.range(),
+                            self.path(&include, resolution),
+                            IncludeErrorKind::TooLarge,
+                        )));
+                        // copies from expanded repeats are left out without reporting again
+                        blocks.clear();
+                        continue;
+                    }
+                    resolution.included_blocks += size;
+                    resolved.extend(blocks.iter().cloned());
+                }
+                Block::Repeat(mut repeat) => {
+                    self.resolve_blocks(repeat.blocks_mut(), resolution, included, diagnostics);
+                    resolved.push(Block::Repeat(repeat));
+                }
+                block => resolved.push(block),
+            }
+        }
+
+        *blocks = resolved;
+    }
+
+    fn include(
+        &self,
+        include: &hir::Include,
+        resolution: &mut Resolution,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Vec<Block> {
+        let path = self.path(include, resolution);
+        let mut error = |kind| {
+            diagnostics.push(Diagnostic::Include(IncludeError::new(
+                include.range(),
+                path.clone(),
+                kind,
+            )))
+        };
+
+        if resolution.stack.contains(&path) {
+            resolution.cycles += 1;
+            error(IncludeErrorKind::Cycle);
+            return Vec::new();
+        }
+
+        let key = (path.clone(), include.repeat_factor());
+        let document = match resolution.lowered.get(&key) {
+            Some(document) => document.clone(),
+            None => {
+                let input = match self.fs.read_to_string(&path) {
+                    Ok(input) => input,
+                    Err(e) => {
+                        error(IncludeErrorKind::Read(e.kind()));
+                        return Vec::new();
+                    }
+                };
+
+                let cycles = resolution.cycles;
+                resolution.stack.push(path.clone());
+                let (workout, diagnostics) =
+                    self.lower(&input, include.repeat_factor(), resolution);
+                resolution.stack.pop();
+
+                let document = Lowered {
+                    blocks: workout.into_blocks(),
+                    diagnostics,
+                };
+                if resolution.cycles == cycles {
+                    resolution.lowered.insert(key, document.clone());
+                }
+                document
+            }
+        };
+
+        if !document.diagnostics.is_empty() {
+            error(IncludeErrorKind::Invalid(document.diagnostics));
+        }
+
+        document.blocks
+    }
+
+    /// Included document relative to the one resolving started from
+    fn path(&self, include: &hir::Include, resolution: &Resolution) -> PathBuf {
+        let including = resolution
+            .stack
+            .last()
+            .and_then(|p| p.parent())
+            .unwrap_or(Path::new(""));
+        normalize(&including.join(include.path()))
+    }
+}
+
+/// Blocks including the ones within repeats
+fn block_count(blocks: &[Block]) -> usize {
+    blocks
+        .iter()
+        .map(|block| match block {
+            Block::Repeat(repeat) => 1 + block_count(repeat.blocks()),
+            _ => 1,
+        })
+        .sum()
+}
+
+/// State of a single [`Resolver::resolve`]
+#[derive(Default)]
+struct Resolution {
+    /// documents being included, innermost last
+    stack: Vec<PathBuf>,
+    /// included documents by path and the repeat factor at the include, so a document
+    /// included from several others is only read and lowered once
+    lowered: HashMap<(PathBuf, usize), Lowered>,
+    /// include cycles found so far, documents cut short by one depend on what
+    /// includes them and aren't kept in `lowered`
+    cycles: usize,
+    /// blocks added by includes so far, up to [`MAX_INCLUDED_BLOCKS`]
+    included_blocks: usize,
+}
+
+#[derive(Clone)]
+struct Lowered {
+    blocks: Vec<Block>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// Remove `.` and `..` without touching the file system, so a document
+/// reached through different relative paths is still caught in a cycle
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir
+                if matches!(
+                    normalized.components().next_back(),
+                    Some(Component::Normal(_))
+                ) =>
+            {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use crate::hir::{LowerErrorKind, RepeatMode};
+
+    use super::*;
+
+    fn files(files: &[(&str, &str)]) -> HashMap<PathBuf, String> {
+        files
+            .iter()
+            .map(|(path, input)| (PathBuf::from(path), input.to_string()))
+            .collect()
+    }
+
+    fn range(start: u32, end: u32) -> TextRange {
+        TextRange::new(start.into(), end.into())
+    }
+
+    fn exercises(workout: &hir::Workout) -> Vec<&str> {
+        workout
+            .set_groups()
+            .filter_map(|sg| sg.exercise())
+            .collect()
+    }
+
+    #[test]
+    fn resolve_includes() {
+        let fs = files(&[
+            (
+                "main.wl",
+                "include \"warmup.wl\"\n# Squat\n225x5\n\n## Upper\ninclude \"lib/arms.wl\"",
+            ),
+            ("warmup.wl", "# Bike\nbw 5:00"),
+            ("lib/arms.wl", "# Curl\n30x12\ninclude \"../warmup.wl\""),
+        ]);
+
+        let (workout, diagnostics) = Resolver::new(fs).resolve(Path::new("main.wl")).unwrap();
+
+        assert_eq!(diagnostics, []);
+        assert_eq!(exercises(&workout), ["Bike", "Squat", "Curl", "Bike"]);
+        assert_eq!(workout.sessions()[0].set_groups().count(), 2);
+    }
+
+    #[test]
+    fn resolve_include_in_repeat() {
+        let fs = files(&[
+            (
+                "main.wl",
+                "repeat 2 {\ninclude \"warmup.wl\"\ninclude \"gone.wl\"\n}",
+            ),
+            ("warmup.wl", "# Bike\nbw 5:00"),
+        ]);
+
+        let options = LowerOptions::default().with_repeat_mode(RepeatMode::Expand);
+        let (workout, diagnostics) = Resolver::new(fs)
+            .with_options(options)
+            .resolve(Path::new("main.wl"))
+            .unwrap();
+
+        assert_eq!(exercises(&workout), ["Bike", "Bike"]);
+        assert_eq!(
+            diagnostics,
+            [Diagnostic::Include(IncludeError::new(
+                range(31, 48),
+                PathBuf::from("gone.wl"),
+                IncludeErrorKind::Read(io::ErrorKind::NotFound)
+            ))]
+        );
+    }
+
+    #[test]
+    fn resolve_include_repeat_cap() {
+        let fs = files(&[
+            ("main.wl", "repeat 1000 {\ninclude \"a.wl\"\n}"),
+            ("a.wl", "repeat 1000 {\n# Squat\n225 x5\n}"),
+        ]);
+
+        let options = LowerOptions::default().with_repeat_mode(RepeatMode::Expand);
+        let (workout, diagnostics) = Resolver::new(fs)
+            .with_options(options)
+            .resolve(Path::new("main.wl"))
+            .unwrap();
+
+        assert_eq!(workout.set_groups().count(), 1000);
+        assert_eq!(
+            diagnostics,
+            [Diagnostic::Include(IncludeError::new(
+                range(14, 28),
+                PathBuf::from("a.wl"),
+                IncludeErrorKind::Invalid(vec![Diagnostic::Lower(LowerError::new(
+                    range(7, 11),
+                    LowerErrorKind::InvalidRepeatCount
+                ))])
+            ))]
+        );
+    }
+
+    #[test]
+    fn resolve_include_fan_out_cap() {
+        // every document includes the next one 10 times, 10^4 set groups in all
+        let includes = |path: &str| format!("include \"{path}\"\n").repeat(10);
+        let fs = files(&[
+            ("main.wl", &includes("a.wl")),
+            ("a.wl", &includes("b.wl")),
+            ("b.wl", &includes("c.wl")),
+            ("c.wl", &includes("d.wl")),
+            ("d.wl", "# Squat\n225x5"),
+        ]);
+
+        let (workout, diagnostics) = Resolver::new(fs).resolve(Path::new("main.wl")).unwrap();
+
+        // the blocks of `c.wl`, `b.wl` and `a.wl` count too, only 8 copies of `a.wl` fit
+        assert_eq!(workout.set_groups().count(), 8000);
+        assert_eq!(
+            diagnostics,
+            [range(120, 134), range(135, 149)].map(|range| Diagnostic::Include(IncludeError::new(
+                range,
+                PathBuf::from("a.wl"),
+                IncludeErrorKind::TooLarge
+            )))
+        );
+    }
+
+    /// Counts the reads of each document
+    struct CountingFileSystem {
+        files: HashMap<PathBuf, String>,
+        reads: RefCell<HashMap<PathBuf, usize>>,
+    }
+
+    impl FileSystem for CountingFileSystem {
+        fn read_to_string(&self, path: &Path) -> io::Result<String> {
+            *self
+                .reads
+                .borrow_mut()
+                .entry(path.to_path_buf())
+                .or_default() += 1;
+            self.files.read_to_string(path)
+        }
+    }
+
+    #[test]
+    fn resolve_shared_include_once() {
+        let fs = CountingFileSystem {
+            files: files(&[
+                ("main.wl", "include \"b.wl\"\ninclude \"c.wl\""),
+                ("b.wl", "# Bench\n185 x5\ninclude \"lib/d.wl\""),
+                ("c.wl", "# Row\n135 x8\ninclude \"./lib/d.wl\""),
+                ("lib/d.wl", "# Curl\n30 x12"),
+            ]),
+            reads: RefCell::default(),
+        };
+        let resolver = Resolver::new(fs);
+
+        let (workout, diagnostics) = resolver.resolve(Path::new("main.wl")).unwrap();
+
+        assert_eq!(diagnostics, []);
+        assert_eq!(exercises(&workout), ["Bench", "Curl", "Row", "Curl"]);
+        assert_eq!(resolver.fs.reads.borrow()[Path::new("lib/d.wl")], 1);
+    }
+
+    #[test]
+    fn resolve_include_cycle() {
+        let fs = files(&[
+            ("a.wl", "# Squat\n225x5\ninclude \"./b.wl\""),
+            ("b.wl", "include \"a.wl\"\n# Bench\n185x5"),
+        ]);
+
+        let (workout, diagnostics) = Resolver::new(fs).resolve(Path::new("a.wl")).unwrap();
+
+        assert_eq!(exercises(&workout), ["Squat", "Bench"]);
+        assert_eq!(
+            diagnostics,
+            [Diagnostic::Include(IncludeError::new(
+                range(14, 30),
+                PathBuf::from("b.wl"),
+                IncludeErrorKind::Invalid(vec![Diagnostic::Include(IncludeError::new(
+                    range(0, 14),
+                    PathBuf::from("a.wl"),
+                    IncludeErrorKind::Cycle
+                ))])
+            ))]
+        );
+    }
+
+    #[test]
+    fn resolve_include_errors() {
+        let fs = files(&[
+            ("main.wl", "# Squat\n225x5\ninclude \"bench.wl\""),
+            ("bench.wl", "# Bench\n185x5 rpe12"),
+        ]);
+
+        let (_, diagnostics) = Resolver::new(fs).resolve(Path::new("main.wl")).unwrap();
+
+        let [Diagnostic::Include(error)] = &diagnostics[..] else {
+            panic!("expected an include error, got {diagnostics:?}");
+        };
+        assert_eq!(error.range(), range(14, 32));
+        assert_eq!(error.path(), Path::new("bench.wl"));
+        let IncludeErrorKind::Invalid(errors) = error.kind() else {
+            panic!("expected errors within the included document");
+        };
+        assert!(matches!(
+            &errors[..],
+            [Diagnostic::Lower(e)] if *e.kind() == LowerErrorKind::InvalidRpe
+        ));
+    }
+
+    #[test]
+    fn resolve_missing_document() {
+        let error = Resolver::new(files(&[]))
+            .resolve(Path::new("main.wl"))
+            .unwrap_err();
+
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+    }
+}
@@ -1,15 +1,22 @@
 use crate::lexer::TokenKind;
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
-pub struct TokenSet(u32);
+pub struct TokenSet(u64);
+
+// one bit per kind, `Error` is the last one
+const _: () = assert!((TokenKind::Error as u8) < u64::BITS as u8);
 
 impl TokenSet {
     pub const fn with_kind(self, kind: TokenKind) -> Self {
-        Self(self.0 | (1 << kind as u32))
+        Self(self.0 | (1 << kind as u64))
+    }
+
+    pub const fn union(self, other: TokenSet) -> Self {
+        Self(self.0 | other.0)
     }
 
     pub const fn is_set(self, kind: TokenKind) -> bool {
-        (self.0 & (1 << kind as u32)) != 0
+        (self.0 & (1 << kind as u64)) != 0
     }
 
     pub const fn from_array<const N: usize>(kinds: [TokenKind; N]) -> Self {